@@ -0,0 +1,8 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn days(c: &mut Criterion) {
+    aoc_common::bench::bench_year(c, &aoc2024::YEAR);
+}
+
+criterion_group!(benches, days);
+criterion_main!(benches);
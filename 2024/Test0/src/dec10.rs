@@ -1,13 +1,15 @@
 use std::collections::HashSet;
-use crate::helpers::{read_matrix, Matrix, Vec2};
+use aoc_common::grid::Grid;
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
-fn trail_score(matrix: &Matrix, start_pos: Vec2) -> i32 {
+fn trail_score(matrix: &Grid<i32>, start_pos: Vec2) -> i32 {
     let mut visited: HashSet<Vec2> = HashSet::new();
     let mut path: Vec<Vec2> = Vec::new();
     let mut score = 0;
 
-    let dirs = vec![ Vec2::new(0, -1), Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(-1, 0), ];
-
     path.push(start_pos);
 
     loop {
@@ -20,19 +22,14 @@ fn trail_score(matrix: &Matrix, start_pos: Vec2) -> i32 {
         }
         visited.insert(cur_pos);
 
-        let cur_height = matrix.get_int(cur_pos).unwrap();
+        let cur_height = matrix[cur_pos];
         if cur_height == 9 {
             score += 1;
             continue;
         }
 
-        for dir in dirs.iter() {
-            let next_pos = cur_pos + *dir;
-            if !matrix.contains(next_pos) {
-                continue;
-            }
-
-            let next_height = matrix.get_int(next_pos).unwrap();
+        for next_pos in matrix.neighbors4(cur_pos) {
+            let next_height = matrix[next_pos];
             if next_height != cur_height + 1 {
                 continue;
             }
@@ -43,13 +40,11 @@ fn trail_score(matrix: &Matrix, start_pos: Vec2) -> i32 {
     score
 }
 
-fn trail_rating(matrix: &Matrix, start_pos: Vec2) -> i32 {
+fn trail_rating(matrix: &Grid<i32>, start_pos: Vec2) -> i32 {
     let mut path: Vec<Vec2> = Vec::new();
     let mut path_set: HashSet<Vec2> = HashSet::new();
     let mut score = 0;
 
-    let dirs = vec![ Vec2::new(0, -1), Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(-1, 0), ];
-
     path.push(start_pos);
 
     loop {
@@ -64,23 +59,18 @@ fn trail_rating(matrix: &Matrix, start_pos: Vec2) -> i32 {
         }
         path_set.insert(cur_pos);
 
-        let cur_height = matrix.get_int(cur_pos).unwrap();
+        let cur_height = matrix[cur_pos];
         if cur_height == 9 {
             score += 1;
             continue;
         }
 
-        for dir in dirs.iter() {
-            let next_pos = cur_pos + *dir;
-            if !matrix.contains(next_pos) {
-                continue;
-            }
-
+        for next_pos in matrix.neighbors4(cur_pos) {
             if path_set.contains(&next_pos) {
                 continue;
             }
 
-            let next_height = matrix.get_int(next_pos).unwrap();
+            let next_height = matrix[next_pos];
             if next_height != cur_height + 1 {
                 continue;
             }
@@ -92,35 +82,37 @@ fn trail_rating(matrix: &Matrix, start_pos: Vec2) -> i32 {
     score
 }
 
-fn compute_result(matrix: &Matrix, rating: bool) -> i32 {
+fn compute_result(matrix: &Grid<i32>, rating: bool) -> i32 {
     let mut result = 0;
-    for y in 0..matrix.height {
-        for x in 0..matrix.width {
-            let pos= Vec2::new(x as i32, y as i32);
-            let val = matrix.get_int(pos).unwrap();
-            if val != 0 {
-                continue;
-            }
-            if rating {
-                result += trail_rating(matrix, pos);
-            } else {
-                result += trail_score(matrix, pos);
-            }
+    for pos in matrix.positions() {
+        if matrix[pos] != 0 {
+            continue;
+        }
+        if rating {
+            result += trail_rating(matrix, pos);
+        } else {
+            result += trail_score(matrix, pos);
         }
     }
     result
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec10() {
-    let matrix = read_matrix("dec10.in.txt").expect("Could not load input.");
-    let result = compute_result(&matrix, false);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec10;
+
+impl Solver for Dec10 {
+    type Input = Grid<i32>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-#[allow(dead_code)]
-pub(crate) fn dec10_2() {
-    let matrix = read_matrix("dec10.in.txt").expect("Could not load input.");
-    let result = compute_result(&matrix, true);
-    println!("{:?}", result);
+    fn parse(&self, lines: &[String]) -> ParseResult<Grid<i32>> {
+        Grid::from_lines(lines, |c| c.to_digit(10).map(|d| d as i32), "height digit")
+    }
+
+    fn part1(&self, matrix: &Grid<i32>) -> Result<i32> {
+        Ok(compute_result(matrix, false))
+    }
+
+    fn part2(&self, matrix: &Grid<i32>) -> Result<i32> {
+        Ok(compute_result(matrix, true))
+    }
 }
@@ -1,5 +1,8 @@
 use std::collections::HashMap;
-use crate::helpers::{parse_nums, read_line};
+use aoc_common::basic_parsing::{parse_nums, single_line};
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 fn compute_step(nums: &Vec<i64>) -> Vec<i64> {
     let mut result = Vec::new();
@@ -28,9 +31,8 @@ fn compute_step_single(num: i64) -> Vec<i64> {
 fn compute_result(nums: Vec<i64>, steps: i64) -> Vec<i64> {
     let mut result = nums;
     //println!("{:?}", result);
-    for step in 0..steps {
+    for _ in 0..steps {
         result = compute_step(&result);
-        println!("{}: {}", step, result.len());
     }
     result
 }
@@ -63,18 +65,22 @@ fn compute_result2(nums: Vec<i64>, steps: i32) -> i64 {
 }
 
 
-#[allow(dead_code)]
-pub(crate) fn dec11() {
-    let content = read_line("dec11.in.txt").expect("Could not load input.");
-    let nums = parse_nums(&content);
-    let result = compute_result(nums, 25);
-    println!("{:?}", result.len());
-}
+pub(crate) struct Dec11;
+
+impl Solver for Dec11 {
+    type Input = Vec<i64>;
+    type Answer1 = usize;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<i64>> {
+        parse_nums(0, single_line(lines)?)
+    }
 
-#[allow(dead_code)]
-pub(crate) fn dec11_2() {
-    let content = read_line("dec11.in.txt").expect("Could not load input.");
-    let nums = parse_nums(&content);
-    let result = compute_result2(nums, 75);
-    println!("{:?}", result);
+    fn part1(&self, nums: &Vec<i64>) -> Result<usize> {
+        Ok(compute_result(nums.clone(), 25).len())
+    }
+
+    fn part2(&self, nums: &Vec<i64>) -> Result<i64> {
+        Ok(compute_result2(nums.clone(), 75))
+    }
 }
@@ -1,100 +1,37 @@
-use std::collections::HashSet;
-use crate::find_union::FindUnion;
-use crate::helpers::{read_matrix, Matrix, Vec2};
+use aoc_common::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::regions::find_regions;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
-static DIRS: [Vec2; 4] = [Vec2::new(0, -1), Vec2::new(0, 1), Vec2::new(-1, 0), Vec2::new(1, 0)];
-
-fn flood(matrix: &Matrix, start_pos: Vec2, mid: char, visited: &mut HashSet<Vec2>) -> (i32, i32) {
-    if visited.contains(&start_pos) {
-        return (0, 0);
-    }
-
-    visited.insert(start_pos);
-
-    let mut peri = 4;
-    let mut arr = 1;
-    for &dir in &DIRS {
-        let new_pos = start_pos + dir;
-        let c = matrix.get(new_pos).unwrap_or('.');
-        if c == mid {
-            let (addper, addarr) = flood(&matrix, new_pos, mid, visited);
-            peri += addper - 1;
-            arr += addarr;
-        }
-    }
-
-    (peri, arr)
+/// Sum over all regions of area times perimeter.
+fn compute_result(matrix: &Matrix) -> i64 {
+    let regions = find_regions(matrix);
+    regions.regions.iter().map(|r| (r.perimeter * r.area) as i64).sum()
 }
 
-fn compute_result(matrix: &Matrix) -> i64 {
-    let mut visited: HashSet<Vec2> = HashSet::new();
-    let mut result = 0;
-    for y in 0..matrix.height {
-        for x in 0..matrix.width {
-            let pos = Vec2::new(x as i32, y as i32);
-            let (peri, arr) = flood(matrix, pos, matrix.get(pos).unwrap(), &mut visited);
-            result += (peri as i64) * (arr as i64);
-        }
-    }
-    result
+/// Sum over all regions of area times number of sides.
+fn compute_result2(matrix: &Matrix) -> i64 {
+    let regions = find_regions(matrix);
+    regions.regions.iter().map(|r| (r.sides * r.area) as i64).sum()
 }
 
-fn flood2(matrix: &Matrix, start_pos: Vec2, mid: char, visited: &mut HashSet<Vec2>, fences: &mut FindUnion<(Vec2, Vec2)>) -> (i32, i32) {
-    if visited.contains(&start_pos) {
-        return (0, 0);
-    }
+pub(crate) struct Dec12;
 
-    visited.insert(start_pos);
+impl Solver for Dec12 {
+    type Input = Matrix;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    let mut peri = 4;
-    let mut arr = 1;
-    for &dir in &DIRS {
-        let new_pos = start_pos + dir;
-        let c = matrix.get(new_pos).unwrap_or('.');
-        if c == mid {
-            let (addper, addarr) = flood2(&matrix, new_pos, mid, visited, fences);
-            peri += addper - 1;
-            arr += addarr;
-        } else {
-            fences.ensure((start_pos, dir));
-            if fences.contains((start_pos + dir.rot_cw(), dir)) {
-                fences.join((start_pos + dir.rot_cw(), dir), (start_pos, dir));
-            }
-            if fences.contains((start_pos + dir.rot_ccw(), dir)) {
-                fences.join((start_pos + dir.rot_ccw(), dir), (start_pos, dir));
-            }
-        }
+    fn parse(&self, lines: &[String]) -> ParseResult<Matrix> {
+        read_matrix_from_lines(lines.to_vec())
     }
 
-    (peri, arr)
-}
-
-fn compute_result2(matrix: &Matrix) -> i64 {
-    let mut visited: HashSet<Vec2> = HashSet::new();
-    let mut result = 0;
-    for y in 0..matrix.height {
-        for x in 0..matrix.width {
-            let mut fences: FindUnion<(Vec2, Vec2)> = FindUnion::new();
-            let pos = Vec2::new(x as i32, y as i32);
-            let (_peri, arr) = flood2(matrix, pos, matrix.get(pos).unwrap(), &mut visited, &mut fences);
-            let sides = fences.get_sets();
-            let num_sides = sides.len();
-            result += (num_sides as i64) * (arr as i64);
-        }
+    fn part1(&self, matrix: &Matrix) -> Result<i64> {
+        Ok(compute_result(matrix))
     }
-    result
-}
-
-#[allow(dead_code)]
-pub(crate) fn dec12() {
-    let matrix = read_matrix("dec12.in.txt").expect("Could not load input.");
-    let result = compute_result(&matrix);
-    println!("{:?}", result);
-}
 
-#[allow(dead_code)]
-pub(crate) fn dec12_2() {
-    let matrix = read_matrix("dec12.in.txt").expect("Could not load input.");
-    let result = compute_result2(&matrix);
-    println!("{:?}", result);
+    fn part2(&self, matrix: &Matrix) -> Result<i64> {
+        Ok(compute_result2(matrix))
+    }
 }
@@ -1,14 +1,21 @@
-use regex::Regex;
-use crate::helpers::{read_lines, Vec2};
+use regex::{Captures, Regex};
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::{parse_token, ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 #[derive(Debug, Clone, Copy)]
-struct Input {
+pub(crate) struct Input {
     a: Vec2<i64>,
     b: Vec2<i64>,
     prize: Vec2<i64>,
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
+fn parse_xy(line_idx: usize, line: &str, cap: &Captures) -> ParseResult<Vec2<i64>> {
+    Ok(Vec2::<i64>::new(parse_token(line_idx, line, &cap["x"])?, parse_token(line_idx, line, &cap["y"])?))
+}
+
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Input>> {
     let mut inputs: Vec<Input> = Vec::new();
     //return Err(format!("Line idx={} (zero-based) {} does not match the rule regex.", 0, 1).into());
 
@@ -22,19 +29,19 @@ fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
             continue;
         }
 
-        let line1 = lines.get(idx + 1).unwrap();
-        let line2 = lines.get(idx + 2).unwrap();
-        let button_a = button_regex.captures(line0).ok_or(format!("Line idx={} (zero-based) '{}' does not match the A button regex.", idx, line0))?;
-        let button_b = button_regex.captures(line1).ok_or(format!("Line idx={} (zero-based) '{}' does not match the B button regex.", idx + 1, line1))?;
-        let prize = prize_regex.captures(line2).ok_or(format!("Line idx={} (zero-based) '{}' does not match the prize regex.", idx + 2, line2))?;
-        idx += 3;
+        let line1 = lines.get(idx + 1).ok_or(ParseError::end_of_input(lines, "button B"))?;
+        let line2 = lines.get(idx + 2).ok_or(ParseError::end_of_input(lines, "prize"))?;
+        let button_a = button_regex.captures(line0).ok_or(ParseError::line(idx, line0, "Button A: X+<x>, Y+<y>"))?;
+        let button_b = button_regex.captures(line1).ok_or(ParseError::line(idx + 1, line1, "Button B: X+<x>, Y+<y>"))?;
+        let prize = prize_regex.captures(line2).ok_or(ParseError::line(idx + 2, line2, "Prize: X=<x>, Y=<y>"))?;
 
         let input = Input {
-            a: Vec2::<i64>::new(button_a["x"].parse::<i64>().unwrap(), button_a["y"].parse::<i64>().unwrap()),
-            b: Vec2::<i64>::new(button_b["x"].parse::<i64>().unwrap(), button_b["y"].parse::<i64>().unwrap()),
-            prize: Vec2::<i64>::new(prize["x"].parse::<i64>().unwrap(), prize["y"].parse::<i64>().unwrap()),
+            a: parse_xy(idx, line0, &button_a)?,
+            b: parse_xy(idx + 1, line1, &button_b)?,
+            prize: parse_xy(idx + 2, line2, &prize)?,
         };
         inputs.push(input);
+        idx += 3;
     }
 
     Ok(inputs)
@@ -140,21 +147,26 @@ fn compute_costs(inputs: &Vec<Input>) -> i64 {
     cost
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec13() {
-    let lines = read_lines("dec13.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = compute_costs(&inputs);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec13;
+
+impl Solver for Dec13 {
+    type Input = Vec<Input>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-#[allow(dead_code)]
-pub(crate) fn dec13_2() {
-    let lines = read_lines("dec13.in.txt").expect("Could not load input.");
-    let mut inputs = parse_input(&lines).unwrap();
-    for input in inputs.iter_mut() {
-        input.prize += Vec2::<i64>::new(10000000000000, 10000000000000);
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Input>> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, inputs: &Vec<Input>) -> Result<i64> {
+        Ok(compute_costs(inputs))
+    }
+
+    fn part2(&self, inputs: &Vec<Input>) -> Result<i64> {
+        let mut inputs = inputs.clone();
+        for input in inputs.iter_mut() {
+            input.prize += Vec2::<i64>::new(10000000000000, 10000000000000);
+        }
+        Ok(compute_costs(&inputs))
     }
-    let result = compute_costs(&inputs);
-    println!("{:?}", result);
 }
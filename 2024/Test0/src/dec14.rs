@@ -1,30 +1,22 @@
-use std::thread;
-use std::time;
-use regex::Regex;
-use crate::helpers::{read_lines, Vec2};
+use itertools::Itertools;
+use aoc_common::basic_parsing::parse_lines_with;
+use aoc_common::cycle::brent;
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
-#[derive(Debug, Clone, Copy)]
-struct Input {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Input {
     p: Vec2<i64>,
     v: Vec2<i64>,
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
-    let mut inputs: Vec<Input> = Vec::new();
-    //return Err(format!("Line idx={} (zero-based) {} does not match the rule regex.", 0, 1).into());
-
-    let in_regex = Regex::new(r"^p=(?<px>-?\d+),(?<py>-?\d+) v=(?<vx>-?\d+),(?<vy>-?\d+)$").unwrap();
-    for (idx, line) in lines.iter().enumerate() {
-        let cap = in_regex.captures(line).ok_or(format!("Line idx={} (zero-based) '{}' does not match the regex.", idx, line))?;
-
-        let input = Input {
-            p: Vec2::<i64>::new(cap["px"].parse::<i64>().unwrap(), cap["py"].parse::<i64>().unwrap()),
-            v: Vec2::<i64>::new(cap["vx"].parse::<i64>().unwrap(), cap["vy"].parse::<i64>().unwrap()),
-        };
-        inputs.push(input);
-    }
-
-    Ok(inputs)
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Input>> {
+    parse_lines_with(lines, "p={px},{py} v={vx},{vy}", |f| Ok(Input {
+        p: Vec2::<i64>::new(f.get("px")?, f.get("py")?),
+        v: Vec2::<i64>::new(f.get("vx")?, f.get("vy")?),
+    }))
 }
 
 fn compute_stuff(inputs: &mut Vec<Input>, size: Vec2<i64>, time: i64) -> i64 {
@@ -62,64 +54,80 @@ fn compute_stuff(inputs: &mut Vec<Input>, size: Vec2<i64>, time: i64) -> i64 {
     ul * ur * bl * br
 }
 
-fn compute_stuff2(inputs: &Vec<Input>, size: Vec2<i64>) -> i64 {
-    /*
-    let mut reference = HashSet::new();
-    for i in 0..size.x {
-        reference.insert(Vec2::new(i, (size.y - 1 - i).abs()));
+/// Size of the room. The example uses a smaller room than the real input.
+fn room_size(inputs: &Vec<Input>) -> Vec2<i64> {
+    let ex_size = Vec2::<i64>::new(11, 7);
+    let in_size = Vec2::<i64>::new(101, 103);
+    if inputs.iter().all(|input| input.p.x < ex_size.x && input.p.y < ex_size.y) { ex_size } else { in_size }
+}
+
+#[allow(dead_code)]
+fn robot_picture(inputs: &Vec<Input>, size: Vec2<i64>, time: i64) -> Vec<u8> {
+    let mut pic = vec![0; (size.x * size.y) as usize];
+    for input in inputs.iter() {
+        let mut pos = input.p + input.v * time;
+        pos.x %= size.x;
+        pos.y %= size.y;
+        if (pos.x < 0) { pos.x += size.x; }
+        if (pos.y < 0) { pos.y += size.y; }
+        pic[(pos.y * size.x + pos.x) as usize] += 1;
     }
-    */
-    for time in 8280..8281 {
-        //let mut pic = reference.clone();
-        let mut pic = vec![0; (size.x * size.y) as usize];
-        for input in inputs.iter() {
-            let mut pos = input.p + input.v * time;
-            pos.x %= size.x;
-            pos.y %= size.y;
-            if (pos.x < 0) { pos.x += size.x; }
-            if (pos.y < 0) { pos.y += size.y; }
-            //pic.remove(&pos);
-            pic[(pos.y * size.x + pos.x) as usize] += 1;
+    pic
+}
+
+#[allow(dead_code)]
+fn print_picture(pic: &Vec<u8>, size: Vec2<i64>) {
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let idx = y * size.x + x;
+            let val = pic[idx as usize];
+            print!("{}", if val > 0 { (('0' as u8) + val) as char } else { ' ' });
         }
         println!();
-        println!();
-        println!();
-        println!();
-        println!("{}", time);
-        println!();
-        println!();
-        for y in 0..size.y {
-            for x in 0..size.x {
-                let idx = y * size.x + x;
-                let val = pic[idx as usize];
-                print!("{}", if val > 0 { (('0' as u8) + val) as char } else { ' ' });
-            }
-            println!();
-        }
-        let ten_millis = time::Duration::from_millis(50);
-        thread::sleep(ten_millis);
     }
-    unreachable!();
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec14() {
-    let lines = read_lines("dec14.in.txt").expect("Could not load input.");
-    let mut inputs = parse_input(&lines).unwrap();
-    let ex_size = Vec2::<i64>::new(11, 7);
-    let in_size = Vec2::<i64>::new(101, 103);
-    let result = compute_stuff(&mut inputs, in_size, 100);
-    println!("{:?}", result);
+/// Robots after one second.
+fn move_robots(inputs: &Vec<Input>, size: Vec2<i64>) -> Vec<Input> {
+    inputs.iter()
+        .map(|input| Input { p: Vec2::new((input.p.x + input.v.x).rem_euclid(size.x), (input.p.y + input.v.y).rem_euclid(size.y)), v: input.v })
+        .collect_vec()
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec14_2() {
-    let lines = read_lines("dec14.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let ex_size = Vec2::<i64>::new(11, 7);
-    let in_size = Vec2::<i64>::new(101, 103);
-    let result = compute_stuff2(&inputs, in_size);
-    println!("{:?}", result);
+/// The christmas tree was found by looking at the pictures (time 8280 for my input).
+/// Robots forming the tree are clustered in one quadrant, so it is the picture with the lowest safety factor.
+/// After a while the robots are back where they started, so only the first cycle needs to be searched.
+fn compute_stuff2(inputs: &Vec<Input>, size: Vec2<i64>) -> Option<i64> {
+    let cycle = brent(inputs.clone(), |robots| move_robots(robots, size));
+    let mut best: Option<(i64, i64)> = None; // Safety factor, time.
+    for time in 0..(cycle.start + cycle.length) as i64 {
+        let safety = compute_stuff(&mut inputs.clone(), size, time);
+        if best.is_none() || safety < best.unwrap().0 {
+            best = Some((safety, time));
+        }
+    }
+    best.map(|(_safety, time)| time)
+}
+
+pub(crate) struct Dec14;
+
+impl Solver for Dec14 {
+    type Input = Vec<Input>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Input>> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, inputs: &Vec<Input>) -> Result<i64> {
+        let mut inputs = inputs.clone();
+        let size = room_size(&inputs);
+        Ok(compute_stuff(&mut inputs, size, 100))
+    }
+
+    fn part2(&self, inputs: &Vec<Input>) -> Result<i64> {
+        let size = room_size(inputs);
+        Ok(compute_stuff2(inputs, size).ok_or("Room is empty.")?)
+    }
 }
@@ -1,7 +1,12 @@
-use crate::dec6::{make_maze, Maze};
+use aoc_common::grid::Grid;
 #[allow(unused_imports)]
-use crate::helpers::{print_maze, read_lines, read_matrix_from_lines, Matrix, Vec2};
-use crate::helpers::separate_by_blank;
+use aoc_common::maze::{make_maze, print_maze, Maze};
+use aoc_common::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::{ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::basic_parsing::separate_by_blank;
+use aoc_common::solver::Solver;
 
 fn compute_result(matrix: &Matrix) -> i64 {
     let mut result = 0;
@@ -141,37 +146,47 @@ fn process_commands2(maze: &mut Maze, commands: &str) -> () {
     }
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec15() {
-    let lines = read_lines("dec15.in.txt").expect("Could not load input.");
-    let (lines0, lines1) = separate_by_blank(&lines);
-    let matrix = read_matrix_from_lines(lines0).unwrap();
-    let mut maze = make_maze(matrix, '@');
-    let commands = lines1.join("").replace("\n", "");
-    process_commands(&mut maze, &commands);
-    let result = compute_result(&maze.matrix);
-    println!("{:?}", result);
-}
-
-
 fn enlarge(matrix: Matrix) -> Matrix {
     let mut bigdata = Vec::new();
-    for line in matrix.data {
+    for line in matrix.lines() {
         let newline = line.replace("#", "##").replace("O", "[]").replace(".", "..").replace("@", "@.");
         bigdata.push(newline);
     }
-    Matrix { width: matrix.width * 2, height: matrix.height, data: bigdata }
+    Matrix { grid: Grid::from_vec(matrix.width * 2, matrix.height, bigdata.concat().into_bytes()) }
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec15_2() {
-    let lines = read_lines("dec15.in.txt").expect("Could not load input.");
-    let (lines0, lines1) = separate_by_blank(&lines);
-    let matrix = read_matrix_from_lines(lines0).unwrap();
-    let bigmatrix = enlarge(matrix);
-    let mut maze = make_maze(bigmatrix, '@');
-    let commands = lines1.join("").replace("\n", "");
-    process_commands2(&mut maze, &commands);
-    let result = compute_result2(&maze.matrix);
-    println!("{:?}", result);
+pub(crate) struct Dec15;
+
+impl Solver for Dec15 {
+    type Input = (Matrix, String);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<(Matrix, String)> {
+        let (lines0, lines1) = separate_by_blank(lines);
+        // Commands follow the warehouse and the blank line.
+        let first_command_idx = lines0.len() + 1;
+        let matrix = read_matrix_from_lines(lines0)?;
+        make_maze(matrix.clone(), '@')?;
+        for (idx, line) in lines1.iter().enumerate() {
+            if let Some(pos) = line.chars().position(|c| !"<>^v".contains(c)) {
+                return Err(ParseError::new(first_command_idx + idx, line, pos, "one of <>^v"));
+            }
+        }
+        let commands = lines1.join("").replace("\n", "");
+        Ok((matrix, commands))
+    }
+
+    fn part1(&self, (matrix, commands): &(Matrix, String)) -> Result<i64> {
+        let mut maze = make_maze(matrix.clone(), '@')?;
+        process_commands(&mut maze, commands);
+        Ok(compute_result(&maze.matrix))
+    }
+
+    fn part2(&self, (matrix, commands): &(Matrix, String)) -> Result<i64> {
+        let bigmatrix = enlarge(matrix.clone());
+        let mut maze = make_maze(bigmatrix, '@')?;
+        process_commands2(&mut maze, commands);
+        Ok(compute_result2(&maze.matrix))
+    }
 }
@@ -1,41 +1,43 @@
-use std::collections::HashSet;
 use itertools::Itertools;
 use regex::Regex;
-use crate::helpers::read_lines;
+use aoc_common::parse_error::{parse_token, ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     a: i64,
     b: i64,
     c: i64,
     program: Vec<i64>,
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Input> {
+fn parse_input(lines: &[String]) -> ParseResult<Input> {
     let register_regex = Regex::new(r"^Register (?:A|B|C): (?<value>\d+)$").unwrap();
     let program_regex = Regex::new(r"^Program: (?<instructions>[0-9,]+)$").unwrap();
-    let line0 = lines.get(0).unwrap();
-    let line1 = lines.get(1).unwrap();
-    let line2 = lines.get(2).unwrap();
-    let line3 = lines.get(3).unwrap();
-    let line4 = lines.get(4).unwrap();
+    let get_line = |idx: usize, what: &str| lines.get(idx).ok_or(ParseError::end_of_input(lines, what));
+    let line0 = get_line(0, "register A")?;
+    let line1 = get_line(1, "register B")?;
+    let line2 = get_line(2, "register C")?;
+    let line3 = get_line(3, "empty line")?;
+    let line4 = get_line(4, "program")?;
 
     if !line3.is_empty() {
-        return Err(format!("Line idx={} (zero-based) should be empty, but is: {}", 3, line3).into());
+        return Err(ParseError::line(3, line3, "empty line"));
     }
 
-    let register_a = register_regex.captures(line0).ok_or(format!("Line idx={} (zero-based) '{}' does not match the register regex.", 0, line0))?;
-    let register_b = register_regex.captures(line1).ok_or(format!("Line idx={} (zero-based) '{}' does not match the register regex.", 1, line1))?;
-    let register_c = register_regex.captures(line2).ok_or(format!("Line idx={} (zero-based) '{}' does not match the register regex.", 2, line2))?;
-    let program = program_regex.captures(line4).ok_or(format!("Line idx={} (zero-based) '{}' does not match the program regex.", 4, line4))?;
+    let register_a = register_regex.captures(line0).ok_or(ParseError::line(0, line0, "Register A: <value>"))?;
+    let register_b = register_regex.captures(line1).ok_or(ParseError::line(1, line1, "Register B: <value>"))?;
+    let register_c = register_regex.captures(line2).ok_or(ParseError::line(2, line2, "Register C: <value>"))?;
+    let program = program_regex.captures(line4).ok_or(ParseError::line(4, line4, "Program: <instructions>"))?;
 
 
     let input = Input {
-        a: register_a["value"].parse::<i64>().unwrap(),
-        b: register_b["value"].parse::<i64>().unwrap(),
-        c: register_c["value"].parse::<i64>().unwrap(),
-        program: program["instructions"].split(',').map(|i| i.parse::<i64>().unwrap()).collect_vec(),
+        a: parse_token(0, line0, &register_a["value"])?,
+        b: parse_token(1, line1, &register_b["value"])?,
+        c: parse_token(2, line2, &register_c["value"])?,
+        program: program["instructions"].split(',').map(|i| parse_token(4, line4, i)).collect::<ParseResult<_>>()?,
     };
 
     Ok(input)
@@ -51,6 +53,7 @@ fn decode_combo_value(program: &Input, a: i64, b: i64, c: i64, val: i64) -> i64
     }
 }
 
+#[allow(dead_code)]
 fn decode_combo_sym(val: i64) -> String {
     match val {
         0 | 1 | 2 | 3 => val.to_string(),
@@ -61,6 +64,7 @@ fn decode_combo_sym(val: i64) -> String {
     }
 }
 
+#[allow(dead_code)]
 fn decode(program: &Input) -> () {
     let mut pc: i64 = 0;
 
@@ -142,36 +146,26 @@ fn from_digits(nums: &Vec<i64>) -> i64 {
     num
 }
 
-fn multi_compute(program: &Input, last_digs: &Vec<i64>) -> i64 {
+/// Searches for 'a' with given last octal digits that makes the program print itself. None if there is no such 'a'.
+fn multi_compute(program: &Input, last_digs: &Vec<i64>) -> Option<i64> {
     let b = program.b;
     let c = program.c;
-    let mut next_candidates: HashSet<i64> = HashSet::new();
+    // Programs shorter than the digits cannot print themselves, e.g. those of the examples.
+    if last_digs.len() >= program.program.len() {
+        return None;
+    }
     for a in 0..300000 {
-        if a % 100000 == 0 {
-            //println!("{:?}", a);
-        }
-
         let nums = to_digits(a);
         let mut effnums = nums.clone();
         effnums.extend(last_digs);
         let effective_a = from_digits(&effnums);
 
         let result = compute_ex(program, effective_a, b, c);
-
-        let num_match = last_digs.len() + 1;
-        let last_res = &result[result.len() - num_match..];
-        let last_prg = &program.program[program.program.len() - num_match..];
-        if last_res == last_prg {
-            println!("{} | {}: {:?} {:?} -> {:?}", a, effective_a, nums, last_digs, result);
-            next_candidates.insert(*nums.last().unwrap());
-        }
         if result == program.program {
-            println!("Success: {:?}", effective_a);
-            return a;
+            return Some(effective_a);
         }
     }
-    println!("Candidates: {:?}", next_candidates);
-    return 0;
+    None
 }
 
 fn multi_compute_b(program: &Input, expected: &Vec<i64>) -> i64 {
@@ -300,48 +294,51 @@ fn multi_compute_prog_try(program: &Input) -> i64 {
     unreachable!()
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec17() {
-    let lines = read_lines("dec17.in.txt").expect("Could not load input.");
-    let mut input = parse_input(&lines).unwrap();
-    let result = compute(&mut input);
-    let sres = result.iter().map(|i| i.to_string()).join(",");
-    println!("{:?}", sres);
-}
+pub(crate) struct Dec17;
 
-#[allow(dead_code)]
-pub(crate) fn dec17_2() {
-    let lines = read_lines("dec17.in.txt").expect("Could not load input.");
-    let input = parse_input(&lines).unwrap();
-    decode(&input);
-    println!("{:?}",compute(&input));
-    //let result = prog0(&input, &vec![4,6,3,5,6,3,5,2,1,0]);
-    //let result = prog(&input, &vec![2,1,0,4,6,2,4,2,0]);
-    //println!("{:?}",input);
-    //println!("{:?}",compute(&mut input.clone()));
-    // Last: 3
-    //       0 or 7
-    //            0: 7, 0, 3 -> 2 or 4
-    //               2: 2, 7, 0, 3 -> 1 or 3 or 4
-    //                  1: 1, 2, 7, 0, 3 -> 2 or 5
-    //                     2: none
-    //                     5: none
-    //                  3: 2, 3, 2, 7, 0, 3 -> none
-    //                  4: none
-    //               4: 1, 4, 7, 0, 3 -> 0 or 5
-    //                  0: 3, 0, 1, 4, 7, 0, 3 -> 2 or 3 or 5
-    //                     2: 1, 2, 3, 0, 1, 4, 7, 0, 3 -> none
-    //                     3: 3, 3, 0, 1, 4, 7, 0, 3 -> 1 or 5
-    //                         success a=109685330781408 res = 107744, [0, 4, 3, 2, 2, 3] [3, 1, 3, 3, 0, 1, 4, 7, 0, 3]
-    //            7: 1, 4, 7, 7, 3 -> 0 or 5
-    //                                     5: none
-    //                                     0: 3, 0, 1, 4, 7, 7, 3 -> 2 or 5 or 3
-    //                                                                  2: 1, 3, 0, 1, 4, 7, 7, 3 -> none
-    //                                                                  5: 3, 1, 5, 3, 0, 1, 4, 7, 7, 3 -> success a=140471689913568 res=107744 not right!
-    //                                                                  3: 3, 3, 0, 1, 4, 7, 7, 3 -> 1 or 5
-    let result = multi_compute(&input, &vec![3, 1, 3, 3, 0, 1, 4, 7, 0, 3]);
-    //let result = multi_compute_prog(&input);
-    //let result = multi_compute_b(&input, &vec![2,4,1,7,7,5,4,1,1,4,5,5,0,3,3,0]);
-    println!("{:?}", result);
-}
+impl Solver for Dec17 {
+    type Input = Input;
+    type Answer1 = String;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Input> {
+        parse_input(lines)
+    }
 
+    fn part1(&self, input: &Input) -> Result<String> {
+        let result = compute(input);
+        Ok(result.iter().map(|i| i.to_string()).join(","))
+    }
+
+    fn part2(&self, input: &Input) -> Result<i64> {
+        //decode(&input);
+        //let result = prog0(&input, &vec![4,6,3,5,6,3,5,2,1,0]);
+        //let result = prog(&input, &vec![2,1,0,4,6,2,4,2,0]);
+        //println!("{:?}",input);
+        //println!("{:?}",compute(&mut input.clone()));
+        // Last: 3
+        //       0 or 7
+        //            0: 7, 0, 3 -> 2 or 4
+        //               2: 2, 7, 0, 3 -> 1 or 3 or 4
+        //                  1: 1, 2, 7, 0, 3 -> 2 or 5
+        //                     2: none
+        //                     5: none
+        //                  3: 2, 3, 2, 7, 0, 3 -> none
+        //                  4: none
+        //               4: 1, 4, 7, 0, 3 -> 0 or 5
+        //                  0: 3, 0, 1, 4, 7, 0, 3 -> 2 or 3 or 5
+        //                     2: 1, 2, 3, 0, 1, 4, 7, 0, 3 -> none
+        //                     3: 3, 3, 0, 1, 4, 7, 0, 3 -> 1 or 5
+        //                         success a=109685330781408 res = 107744, [0, 4, 3, 2, 2, 3] [3, 1, 3, 3, 0, 1, 4, 7, 0, 3]
+        //            7: 1, 4, 7, 7, 3 -> 0 or 5
+        //                                     5: none
+        //                                     0: 3, 0, 1, 4, 7, 7, 3 -> 2 or 5 or 3
+        //                                                                  2: 1, 3, 0, 1, 4, 7, 7, 3 -> none
+        //                                                                  5: 3, 1, 5, 3, 0, 1, 4, 7, 7, 3 -> success a=140471689913568 res=107744 not right!
+        //                                                                  3: 3, 3, 0, 1, 4, 7, 7, 3 -> 1 or 5
+        let result = multi_compute(input, &vec![3, 1, 3, 3, 0, 1, 4, 7, 0, 3]);
+        //let result = multi_compute_prog(&input);
+        //let result = multi_compute_b(&input, &vec![2,4,1,7,7,5,4,1,1,4,5,5,0,3,3,0]);
+        Ok(result.ok_or("No value of register A makes the program print itself.")?)
+    }
+}
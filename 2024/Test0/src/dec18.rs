@@ -1,81 +1,62 @@
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
+use itertools::Itertools;
 use regex::Regex;
-use crate::dec6::Direction;
-use crate::helpers::{read_lines, Matrix, Vec2};
-
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Vec2>> {
-    let mut result: Vec<Vec2> = Vec::new();
-    for (idx, line) in lines.iter().enumerate() {
-        let err = format!("Line idx={} (zero-based) '{}' does not match the point format.", idx, line);
-        let (x, y) = line.split_once(',').ok_or(err.clone())?;
-        result.push(Vec2::new(x.parse()?, y.parse()?));
-    }
-
-    Ok(result)
+use aoc_common::basic_parsing::parse_lines_with;
+use aoc_common::matrix::Matrix;
+use aoc_common::obstacles::first_blocking_wall;
+use aoc_common::search::bfs;
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
+
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Vec2>> {
+    parse_lines_with(lines, "{x},{y}", |f| Ok(Vec2::new(f.get("x")?, f.get("y")?)))
 }
 
+/// Bytes outside of the memory are ignored, as in 'first_blocking_wall'.
 fn best_path(matrix: &Matrix, bad_ones: &[Vec2], start: Vec2, end: Vec2) -> Option<i64> {
-    let mut matrix = matrix.clone();
-    for &bad in bad_ones {
-        matrix.put(bad, '#');
-    }
-    println!("{}", bad_ones.len());
-    //println!("{}", matrix);
+    let bad_ones: HashSet<Vec2> = bad_ones.iter().copied().collect();
+    let is_open = |pos: Vec2| matrix.get(pos).is_some_and(|c| c != '#') && !bad_ones.contains(&pos);
+    let successors = |&pos: &Vec2| matrix.neighbors4(pos).filter(|&next_pos| is_open(next_pos)).collect_vec();
+    let result = bfs([start], successors, |&pos| pos == end);
+    result.goal_dist().map(|dist| dist as i64)
+}
 
-    let mut wavefront = Vec::<Vec2>::new();
-    let mut best_dists: HashMap<Vec2, i64> = HashMap::new();
-    wavefront.push(start);
-    best_dists.insert(start, 0);
-    let mut idx = 0;
+fn find_blocker(matrix: &Matrix, bad_ones: &[Vec2], start: Vec2, end: Vec2) -> Option<Vec2> {
+    let idx = first_blocking_wall(matrix, |&c| c != b'#', bad_ones, start, end)?;
+    Some(bad_ones[idx])
+}
 
-    loop {
-        if idx >= wavefront.len() {
-            return None; //panic!("No path to end found.");
-        }
+/// Memory size and number of fallen bytes in part 1. The example uses a smaller memory than the real input.
+fn memory_size(input: &Vec<Vec2>) -> (usize, usize) {
+    if input.iter().all(|p| p.x < 7 && p.y < 7) { (7, 12) } else { (71, 1024) }
+}
 
-        let pos = wavefront[idx];
-        idx += 1;
-        let &dist = best_dists.get(&pos).unwrap();
+pub(crate) struct Dec18;
 
-        if pos == end { return Some(dist); }
+impl Solver for Dec18 {
+    type Input = Vec<Vec2>;
+    type Answer1 = i64;
+    type Answer2 = String;
 
-        for dir in [Direction::Up, Direction::Down, Direction::Left, Direction::Right] {
-            let next_pos = pos + dir.dir();
-            if best_dists.contains_key(&next_pos) { continue; }
-            let val = matrix.get(next_pos);
-            if val == None { continue; }
-            if val.unwrap() == '#' { continue; }
-            wavefront.push(next_pos);
-            best_dists.insert(next_pos, dist + 1);
-        }
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Vec2>> {
+        parse_input(lines)
     }
-}
 
-fn find_blocker(matrix: &Matrix, bad_ones: &[Vec2], start: Vec2, end: Vec2) -> Option<Vec2> {
-    for idx in 0..bad_ones.len() {
-        let result = best_path(&matrix, &bad_ones[0..idx + 1], start, end);
-        if result.is_none() { return Some(bad_ones[idx]); }
+    fn part1(&self, input: &Vec<Vec2>) -> Result<i64> {
+        let (size, fallen) = memory_size(input);
+        let matrix = Matrix::new(size, size, '.', None);
+        let fallen = fallen.min(input.len());
+        let result = best_path(&matrix, &input[..fallen], Vec2::new(0, 0), Vec2::new(matrix.width as i32 - 1, matrix.height as i32 - 1));
+        Ok(result.ok_or("No path to the exit.")?)
     }
-    None
-}
 
-#[allow(dead_code)]
-pub(crate) fn dec18() {
-    let lines = read_lines("dec18.in.txt").expect("Could not load input.");
-    let mut input = parse_input(&lines).unwrap();
-    let matrix = Matrix::new(71, 71, '.', None);
-    println!("{}", matrix);
-    input.split_off(1024);
-    let result = best_path(&matrix, &input, Vec2::new(0, 0), Vec2::new(matrix.width as i32 - 1, matrix.height as i32 - 1));
-    println!("{:?}", result);
-}
-
-#[allow(dead_code)]
-pub(crate) fn dec18_2() {
-    let lines = read_lines("dec18.in.txt").expect("Could not load input.");
-    let input = parse_input(&lines).unwrap();
-    let matrix = Matrix::new(71, 71, '.', None);
-    println!("{}", matrix);
-    let result = find_blocker(&matrix, &*input, Vec2::new(0, 0), Vec2::new(matrix.width as i32 - 1, matrix.height as i32 - 1));
-    println!("{:?}", result);
+    fn part2(&self, input: &Vec<Vec2>) -> Result<String> {
+        let (size, _fallen) = memory_size(input);
+        let matrix = Matrix::new(size, size, '.', None);
+        let result = find_blocker(&matrix, input, Vec2::new(0, 0), Vec2::new(matrix.width as i32 - 1, matrix.height as i32 - 1));
+        let blocker = result.ok_or("Exit is never blocked.")?;
+        Ok(format!("{},{}", blocker.x, blocker.y))
+    }
 }
@@ -1,19 +1,24 @@
 use std::collections::{HashMap, HashSet};
-use crate::dec6::make_maze;
-use crate::helpers::{read_lines, read_matrix_from_lines, separate_by_blank};
+use aoc_common::maze::make_maze;
+use aoc_common::basic_parsing::separate_by_blank;
+use aoc_common::matrix::read_matrix_from_lines;
+use aoc_common::parse_error::{ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     patterns: Vec<String>,
     designs: Vec<String>,
 }
 
-fn parse_input(lines: &Vec<String>) -> Input {
+fn parse_input(lines: &[String]) -> ParseResult<Input> {
     let (lines0, lines1) = separate_by_blank(lines);
-    Input {
-        patterns: lines0[0].split(',').map(|s| s.trim().to_string()).collect(),
+    let patterns = lines0.first().ok_or(ParseError::end_of_input(lines, "towel patterns"))?;
+    Ok(Input {
+        patterns: patterns.split(',').map(|s| s.trim().to_string()).collect(),
         designs: lines1.iter().map(|s| s.trim().to_string()).collect(),
-    }
+    })
 }
 
 fn compute_one_possible(patterns: &Vec<String>, design: &String) -> bool {
@@ -79,18 +84,22 @@ fn compute_possible2(input: &Input) -> i64 {
     result
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec19() {
-    let lines = read_lines("dec19.in.txt").expect("Could not load input.");
-    let input = parse_input(&lines);
-    let result = compute_possible(&input);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec19;
+
+impl Solver for Dec19 {
+    type Input = Input;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Input> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, input: &Input) -> Result<i64> {
+        Ok(compute_possible(input))
+    }
 
-#[allow(dead_code)]
-pub(crate) fn dec19_2() {
-    let lines = read_lines("dec19.in.txt").expect("Could not load input.");
-    let input = parse_input(&lines);
-    let result = compute_possible2(&input);
-    println!("{:?}", result);
+    fn part2(&self, input: &Input) -> Result<i64> {
+        Ok(compute_possible2(input))
+    }
 }
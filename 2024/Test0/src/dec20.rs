@@ -1,214 +1,73 @@
-use std::collections::{HashMap, HashSet};
-use crate::dec6::{make_maze, Direction, Maze};
-use crate::helpers::{find_single_points, print_matrix, read_lines, read_matrix, Matrix, Vec2};
+use std::collections::HashSet;
+use aoc_common::cheats::RaceTrack;
+use aoc_common::matrix::{find_single_points, read_matrix_from_lines, Matrix};
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
-fn shortest_path(matrix: &Matrix, start_pos: Vec2, end_pos: Vec2) -> i64 {
-    let mut best_distances : HashMap<Vec2, i64> = HashMap::new();
-    let mut wavefront: Vec<Vec2> = Vec::new();
-    wavefront.push(start_pos);
-    best_distances.insert(start_pos, 0);
-    loop {
-        if wavefront.is_empty() {
-            panic!("No path to end.");
-        }
-
-        let mut new_wavefront = Vec::new();
-        for pos in wavefront {
-            let dist = *best_distances.get(&pos).unwrap();
-            for dir in [Direction::Left, Direction::Right, Direction::Up, Direction::Down] {
-                let next_pos = pos + dir.dir();
-                let val = matrix.get(next_pos);
-                if val.is_none() { continue; }
-                if val == Some('#') { continue; }
-                if next_pos == end_pos {
-                    return dist + 1;
-                }
-                if best_distances.contains_key(&next_pos) { continue; }
-                new_wavefront.push(next_pos);
-                best_distances.insert(next_pos, dist + 1);
-            }
-        }
-        wavefront = new_wavefront;
-    }
+/// Race track with start and end positions. Start and end are replaced by track.
+pub(crate) struct Track {
+    matrix: Matrix,
+    start_pos: Vec2,
+    end_pos: Vec2,
 }
 
-fn shortest_paths_to_walls(matrix: &Matrix, start_pos: Vec2) -> HashMap<Vec2, i64> {
-    let mut best_distances : HashMap<Vec2, i64> = HashMap::new();
-    let mut best_wall_distances : HashMap<Vec2, i64> = HashMap::new();
-    let mut wavefront: Vec<Vec2> = Vec::new();
-    wavefront.push(start_pos);
-    best_distances.insert(start_pos, 0);
-    loop {
-        if wavefront.is_empty() {
-            break;
-        }
+pub(crate) struct Dec20;
 
-        let mut new_wavefront = Vec::new();
-        for pos in wavefront {
-            let dist = *best_distances.get(&pos).unwrap();
-            for dir in [Direction::Left, Direction::Right, Direction::Up, Direction::Down] {
-                let next_pos = pos + dir.dir();
-                let val = matrix.get(next_pos);
-                if val.is_none() { continue; }
-                if val == Some('#') {
-                    if !best_wall_distances.contains_key(&next_pos) {
-                        best_wall_distances.insert(next_pos, dist + 1);
-                    }
-                    continue;
-                }
-                if best_distances.contains_key(&next_pos) { continue; }
-                new_wavefront.push(next_pos);
-                best_distances.insert(next_pos, dist + 1);
-            }
-        }
-        wavefront = new_wavefront;
-    }
-    best_wall_distances
-}
+impl Solver for Dec20 {
+    type Input = Track;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-fn shortest_paths_to_walls2(matrix: &Matrix, start_pos: Vec2) -> HashMap<Vec2, i64> {
-    let mut best_distances : HashMap<Vec2, i64> = HashMap::new();
-    let mut best_wall_distances : HashMap<Vec2, i64> = HashMap::new();
-    let mut wavefront: Vec<Vec2> = Vec::new();
-    wavefront.push(start_pos);
-    best_distances.insert(start_pos, 0);
-    loop {
-        if wavefront.is_empty() {
-            break;
-        }
-
-        let mut new_wavefront = Vec::new();
-        for pos in wavefront {
-            let dist = *best_distances.get(&pos).unwrap();
-            for dir in [Direction::Left, Direction::Right, Direction::Up, Direction::Down] {
-                let next_pos = pos + dir.dir();
-                let val = matrix.get(next_pos);
-                if val.is_none() { continue; }
-                if val == Some('#') {
-                    if !best_wall_distances.contains_key(&pos) {
-                        best_wall_distances.insert(pos, dist);
-                    }
-                    continue;
-                }
-                if best_distances.contains_key(&next_pos) { continue; }
-                new_wavefront.push(next_pos);
-                best_distances.insert(next_pos, dist + 1);
-            }
-        }
-        wavefront = new_wavefront;
+    fn parse(&self, lines: &[String]) -> ParseResult<Track> {
+        let mut matrix = read_matrix_from_lines(lines.to_vec())?;
+        let points = find_single_points(&mut matrix, Some('.'), &HashSet::from(['S', 'E']), true)?;
+        let start_pos = points[&'S'];
+        let end_pos = points[&'E'];
+        Ok(Track { matrix, start_pos, end_pos })
     }
-    best_wall_distances
-}
-
-fn shortest_path_cheats(matrix: &Matrix, start: Vec2, max_cheats: i32) -> Vec<(Option<i32>, i64)> {
-    let mut best_distances : HashMap<Vec2, (Option<i32>, i64)> = HashMap::new(); // pos -> (cheats, dist from start)
-    let mut visited : HashSet<(Option<i32>, Vec2)> = HashSet::new(); // (cheats, pos)
-    let mut wavefront: Vec<Vec2> = Vec::new();
-    wavefront.push(start);
-    visited.insert((None, start));
-    best_distances.insert(start, (None, 0));
-    let mut ends: Vec<(Option<i32>, i64)> = Vec::new();
-    loop {
-        if wavefront.is_empty() {
-            break;
-        }
 
-        let mut new_wavefront = Vec::new();
-        for pos in wavefront {
-            let (cheats, dist) = *best_distances.get(&pos).unwrap();
-            for dir in [Direction::Left, Direction::Right, Direction::Up, Direction::Down] {
-                let next_pos = pos + dir.dir();
-                let val = matrix.get(next_pos);
-                if val.is_none() { continue; }
-                let mut next_cheats;
-                if val == Some('#') {
-                    match cheats {
-                        None => next_cheats = Some(1),
-                        Some(v@max_cheats) => { continue; },
-                        Some(v) => { next_cheats = Some(v + 1); }
-                    }
-                } else {
-                    match cheats {
-                        None => next_cheats = None,
-                        Some(v@max_cheats) => next_cheats = cheats,
-                        Some(v) => { next_cheats = Some(v + 1); }
-                    }
-                }
-                if val == Some('E') {
-                    ends.push((next_cheats, dist + 1));
-                    continue;
-                }
-                if visited.contains(&(next_cheats, next_pos)) { continue; }
-                new_wavefront.push(next_pos);
-                best_distances.insert(next_pos, (next_cheats, dist + 1));
-            }
-        }
-        wavefront = new_wavefront;
+    fn part1(&self, track: &Track) -> Result<usize> {
+        let Track { matrix, start_pos, end_pos } = track;
+        let race_track = RaceTrack::new(matrix, *start_pos, *end_pos).ok_or("No path to end.")?;
+        Ok(race_track.count_cheats(2, 100))
     }
 
-    ends
-}
-
-fn find_best_shortcuts(swalls: &HashMap<Vec2, i64>, ewalls: &HashMap<Vec2, i64>, max_len: i64) -> Vec<(Vec2, i64)> {
-    let mut result: Vec<(Vec2, i64)> = Vec::new();
-    for (spos, sdist) in swalls {
-        if !ewalls.contains_key(spos) { continue; }
-        let &edist = ewalls.get(spos).unwrap();
-        let dist = sdist + edist;
-        if dist > max_len { continue; }
-        result.push((*spos, dist));
+    fn part2(&self, track: &Track) -> Result<usize> {
+        let Track { matrix, start_pos, end_pos } = track;
+        let race_track = RaceTrack::new(matrix, *start_pos, *end_pos).ok_or("No path to end.")?;
+        Ok(race_track.count_cheats(20, 100))
     }
-    result
 }
 
-fn find_best_shortcuts2(swalls: &HashMap<Vec2, i64>, ewalls: &HashMap<Vec2, i64>, cheat_dist: i64, max_len: i64) -> Vec<(Vec2, i64)> {
-    let mut result: Vec<(Vec2, i64)> = Vec::new();
-    for (spos, sdist) in swalls {
-        for (epos, edist) in ewalls.iter() {
-            let mdist = ((epos.x - spos.x).abs() + (epos.y - spos.y).abs()) as i64;
-            if mdist > cheat_dist { continue; }
-            let dist = sdist + mdist + edist;
-            if dist > max_len { continue; }
-            result.push((*spos, dist));
-        }
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::path::Path;
+    use aoc_common::basic_parsing::read_lines;
+    use aoc_common::inputs::locate_input;
+    use super::*;
+
+    /// Puzzle answers count only the big savings, the example lists the small ones.
+    fn example_track() -> RaceTrack {
+        let lines = read_lines(locate_input(Path::new(crate::INPUT_DIR), 20, "ex").unwrap()).unwrap();
+        let Track { matrix, start_pos, end_pos } = Dec20.parse(&lines).unwrap();
+        RaceTrack::new(&matrix, start_pos, end_pos).unwrap()
     }
-    result
-}
 
-#[allow(dead_code)]
-pub(crate) fn dec20() {
-    let mut matrix = read_matrix("dec20.in.txt").expect("Could not load input.");
-    //let mut maze = make_maze(matrix, 'S');
-    let points = find_single_points(&mut matrix, Some('.'), &HashSet::from(['S', 'E']), true);
-    let start_pos = points[&'S'];
-    let end_pos = points[&'E'];
-    print_matrix(&matrix, &hashmap! {});
-    let spath = shortest_path(&matrix, start_pos, end_pos);
-    //let ends = shortest_path_cheats(&matrix, start_pos, end_pos, 1);
-    let swalls = shortest_paths_to_walls(&matrix, start_pos);
-    let ewalls = shortest_paths_to_walls(&matrix, end_pos);
-    let best_shortcuts = find_best_shortcuts(&swalls, &ewalls, spath - 100);
-    println!("{:?}", spath);
-    println!("{:?}", best_shortcuts.len());
-    //println!("{:?}", swalls);
-    //println!("{:?}", ewalls);
-}
+    #[test]
+    fn example_histograms() {
+        let race_track = example_track();
+        assert_eq!(race_track.best, 84);
+        let expected = BTreeMap::from([(2, 14), (4, 14), (6, 2), (8, 4), (10, 2), (12, 3), (20, 1), (36, 1), (38, 1), (40, 1), (64, 1)]);
+        assert_eq!(race_track.histogram(2, 1), expected);
 
-#[allow(dead_code)]
-pub(crate) fn dec20_2() {
-    let mut matrix = read_matrix("dec20.in.txt").expect("Could not load input.");
-    //let mut maze = make_maze(matrix, 'S');
-    let points = find_single_points(&mut matrix, Some('.'), &HashSet::from(['S', 'E']), true);
-    let start_pos = points[&'S'];
-    let end_pos = points[&'E'];
-    print_matrix(&matrix, &hashmap! {});
-    let spath = shortest_path(&matrix, start_pos, end_pos);
-    //let ends = shortest_path_cheats(&matrix, start_pos, end_pos, 1);
-    let swalls = shortest_paths_to_walls2(&matrix, start_pos);
-    let ewalls = shortest_paths_to_walls2(&matrix, end_pos);
-    let best_shortcuts = find_best_shortcuts2(&swalls, &ewalls, 20, spath - 100);
-    println!("{:?}", spath);
-    println!("{:?}", best_shortcuts.len());
-    //println!("{:?}", swalls);
-    //println!("{:?}", ewalls);
+        let expected = BTreeMap::from([
+            (50, 32), (52, 31), (54, 29), (56, 39), (58, 25), (60, 23), (62, 20),
+            (64, 19), (66, 12), (68, 14), (70, 12), (72, 22), (74, 4), (76, 3),
+        ]);
+        assert_eq!(race_track.histogram(20, 50), expected);
+        assert_eq!(race_track.count_cheats(20, 50), expected.values().sum::<usize>());
+    }
 }
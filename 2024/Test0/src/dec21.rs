@@ -1,7 +1,10 @@
 use std::cmp;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::hash::Hash;
-use crate::helpers::{read_lines, Vec2};
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 type MapType = HashMap<char, HashMap<char, Vec<String>>>;
 
@@ -175,13 +178,7 @@ fn compute_one(line: &String, bot_map: &MapType, door_map: &MapType) -> i64 {
     //let bot2 = map_str(&bot1, bot_map);
     let man = map_str(&bot1, bot_map);
 
-    println!("bot0: {bot0}");
-    println!("bot1: {bot1}");
-    //println!("bot2: {bot2}");
-    println!("man:  {man}");
-
     let res = man.len() as i64 * code;
-    println!("res:  {} * {code} = {res}", man.len());
     res
 }
 
@@ -335,7 +332,6 @@ fn simulate_input(input: &str) -> Option<String> {
     let last_inputs: LastInputsState = vec![Vec::new(); states.len()];
     for c in input.chars() {
         let new_state = simulate(&states, &last_inputs, c);
-        //println!("{} -> {:?}", c, new_state);
         if new_state.is_none() { return None; }
         let (new_states, out, out_inputs) = new_state.unwrap();
         states = new_states;
@@ -354,15 +350,10 @@ fn simulate_bfs(desired_output: &str, zero: &State) -> Option<String> {
 
     let mut found_len = 0;
 
-    let mut counter = 1;
-
     loop {
         if queue.is_empty() { return None; }
         let (path, states, last_inputs, output) = queue.pop_front().unwrap();
-        println!("path: {path:?} output: {output:?}");
         if output == desired_output {
-            println!("Number of nodes visited: {}", counter);
-            println!("Number of nodes visited2: {}", visited.len());
             return Some(path);
         }
         if output.len() < found_len { continue; }
@@ -384,7 +375,6 @@ fn simulate_bfs(desired_output: &str, zero: &State) -> Option<String> {
             if new_output.len() < found_len { continue; }
 
             queue.push_back((path.clone() + &c.to_string(), new_states, out_inputs, new_output));
-            counter += 1;
         }
     }
 }
@@ -395,7 +385,6 @@ fn compute_one2(line: &String, zero: &State) -> i64 {
     let result = simulate_bfs(&line, zero).expect(format!("Could not produce desired code: {}", line).as_str());
 
     let res = result.len() as i64 * code;
-    println!("res:  {} * {code} = {res}", result.len());
     res
 }
 
@@ -409,21 +398,35 @@ fn compute2(lines: &Vec<String>, zero: &State) -> i64 {
     result
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec21() {
-    let lines = read_lines("dec21.xx.txt").expect("Could not load input.");
+pub(crate) struct Dec21;
 
-    let result = simulate_bfs(&lines[0], &zero_state_max(2, false)).expect(format!("Could not produce desired code.").as_str());
-    //let result = simulate_bfs("029A");
+/// Only part 1 is solved.
+impl Solver for Dec21 {
+    type Input = Vec<String>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    //let result = compute2(&lines, &zero_state_max(2, true)); // day 1
-    println!("{:?}", result);
-    /*
-    for line in lines {
-        let out = simulate_input(&line);
-        println!("{:?}", out);
+    const PARTS: u32 = 1;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<String>> {
+        Ok(lines.to_vec())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<i64> {
+        //let result = simulate_bfs(&lines[0], &zero_state_max(2, false));
+        //let result = simulate_bfs("029A");
+        Ok(compute2(lines, &zero_state_max(2, true)))
+        /*
+        for line in lines {
+            let out = simulate_input(&line);
+            println!("{:?}", out);
+        }
+        */
+    }
+
+    fn part2(&self, _lines: &Vec<String>) -> Result<i64> {
+        Err("Part 2 is not solved.".into())
     }
-    */
 }
 
 // 135260 - too high.
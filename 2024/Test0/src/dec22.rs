@@ -1,6 +1,8 @@
 use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
-use crate::helpers::read_lines;
+use aoc_common::parse_error::{parse_token, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 
 fn prune(val: i64) -> i64 {
@@ -40,7 +42,6 @@ fn compute(nums: &Vec<i64>, steps: i64) -> i64 {
     let mut result = 0;
     for &num in nums {
         let res = compute_one(num, steps);
-        println!("{}: {:?}", num, res);
         result += res;
     }
     result
@@ -66,15 +67,12 @@ fn compute_sec_to_ban(prices: &Vec<i32>) -> SecToBan {
 }
 
 fn find_best_pattern(sec_to_bans: &Vec<SecToBan>) -> (Sec, i32) {
-    println!("Computing all sequences.");
     //let all_secs: HashSet<Sec> = sec_to_bans.iter().fold(HashSet::<Sec>::new(), |mut s, sb| {s.extend(sb.keys()); s});
     //println!("Num sequences: {}", all_secs.len());
     let mut best_seq: Sec = (-1, -1, -1, -1);
     let mut best_price = -1;
-    println!("Finding best sequence.");
     //for seq in all_secs {
     for a in -9..10 {
-        println!("a: {a}");
         for b in -9..10 {
             for c in -9..10 {
                 for d in -9..10 {
@@ -94,32 +92,28 @@ fn find_best_pattern(sec_to_bans: &Vec<SecToBan>) -> (Sec, i32) {
 }
 
 fn compute2(nums: &Vec<i64>, steps: i64) -> i64 {
-    println!("Computing prices.");
     let all_prices = nums.iter().map(|&num| collect_one(num, steps)).collect_vec();
-    println!("Computing sec_to_bans.");
     let sec_to_bans: Vec<SecToBan> = all_prices.iter().map(|prices| compute_sec_to_ban(prices)).collect_vec();
-    println!("Finding best pattern.");
     let (seq, price) = find_best_pattern(&sec_to_bans);
-    println!("{:?} {:?}", seq, price);
     price as i64
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec22() {
-    let mut res = 123;
-    for i in 0..10 {
-        res = step(res);
-        println!("{:?}", res);
+pub(crate) struct Dec22;
+
+impl Solver for Dec22 {
+    type Input = Vec<i64>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<i64>> {
+        lines.iter().enumerate().map(|(idx, l)| parse_token(idx, l, l)).collect()
+    }
+
+    fn part1(&self, nums: &Vec<i64>) -> Result<i64> {
+        Ok(compute(nums, 2000))
+    }
+
+    fn part2(&self, nums: &Vec<i64>) -> Result<i64> {
+        Ok(compute2(nums, 2000))
     }
-    let lines = read_lines("dec22.in.txt").expect("Could not load input.");
-    let nums: Vec<i64> = lines.iter().map(|l| l.parse().unwrap()).collect();
-    let result = compute(&nums, 2000);
-    println!("{:?}", result);
-}
-#[allow(dead_code)]
-pub(crate) fn dec22_2() {
-    let lines = read_lines("dec22.in.txt").expect("Could not load input.");
-    let nums: Vec<i64> = lines.iter().map(|l| l.parse().unwrap()).collect();
-    let result = compute2(&nums, 2000);
-    println!("{:?}", result);
 }
@@ -1,5 +1,6 @@
-use crate::dec24::Op;
-use crate::helpers::read_lines;
+use aoc_common::parse_error::{ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 type Comb = [i8; 5];
 
@@ -11,12 +12,12 @@ fn is_match(key: &Comb, lock: &Comb) -> bool {
 }
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     keys: Vec<Comb>,
     locks: Vec<Comb>,
 }
 
-fn parse_input(lines: &Vec<String>) -> Input {
+fn parse_input(lines: &[String]) -> ParseResult<Input> {
     let mut input = Input {
         keys: Vec::new(),
         locks: Vec::new(),
@@ -24,30 +25,36 @@ fn parse_input(lines: &Vec<String>) -> Input {
 
     let mut idx = 0;
     loop {
-        if idx >= lines.len() { return input; }
+        if idx >= lines.len() { return Ok(input); }
 
         let line = lines.get(idx).unwrap();
         idx += 1;
         if line.is_empty() { continue; }
+        if line != "....." && line != "#####" {
+            return Err(ParseError::line(idx - 1, line, "..... (key) or ##### (lock)"));
+        }
         let is_key = line == ".....";
 
         let mut comb: Comb = [0; 5];
         for i in 0..5 {
-            let line = lines.get(idx).unwrap();
-            idx += 1;
+            let line = lines.get(idx).ok_or(ParseError::end_of_input(lines, "row of the pins"))?;
             for (j, c) in line.chars().enumerate() {
+                if j >= comb.len() {
+                    return Err(ParseError::new(idx, line, j, "end of line after 5 pins"));
+                }
                 if c == '#' {
                     comb[j] += 1;
                 }
             }
+            idx += 1;
         }
 
-        let line = lines.get(idx).unwrap();
-        idx += 1;
+        let line = lines.get(idx).ok_or(ParseError::end_of_input(lines, "last row of the pins"))?;
         let exp = (if is_key { "#####" } else { "....." });
         if line != exp {
-            panic!("Expected {} but got {}", exp, line);
+            return Err(ParseError::line(idx, line, exp));
         }
+        idx += 1;
 
         (if is_key { &mut input.keys } else { &mut input.locks }).push(comb);
     }
@@ -65,10 +72,25 @@ fn brute_match(input: &Input) -> i32 {
     result
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec25() {
-    let lines = read_lines("dec25.in.txt").expect("Could not load input.");
-    let input = parse_input(&lines);
-    let result = brute_match(&input);
-    println!("{:?}", result);
+pub(crate) struct Dec25;
+
+/// Day 25 has only one part.
+impl Solver for Dec25 {
+    type Input = Input;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    const PARTS: u32 = 1;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Input> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, input: &Input) -> Result<i32> {
+        Ok(brute_match(input))
+    }
+
+    fn part2(&self, _input: &Input) -> Result<i32> {
+        Err("Day 25 has no part 2.".into())
+    }
 }
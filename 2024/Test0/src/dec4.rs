@@ -1,5 +1,9 @@
 
-use crate::helpers::{read_matrix, Matrix, Vec2};
+use aoc_common::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 fn match_word(matrix: &Matrix, word: &str, x: i32, y: i32, dx: i32, dy: i32) -> bool {
     for (idx, wc) in word.chars().enumerate() {
@@ -55,18 +59,22 @@ fn match_matrix_2(matrix: &Matrix) -> i32 {
     res
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec4() {
-    let matrix = read_matrix("dec4.in.txt").expect("Could not load input.");
-    //println!("{:?}", matrix);
-    let res = match_matrix(&matrix, "XMAS");
-    println!("{:?}", res);
-}
+pub(crate) struct Dec4;
+
+impl Solver for Dec4 {
+    type Input = Matrix;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Matrix> {
+        read_matrix_from_lines(lines.to_vec())
+    }
 
-#[allow(dead_code)]
-pub(crate) fn dec4_2() {
-    let matrix = read_matrix("dec4.in.txt").expect("Could not load input.");
-    //println!("{:?}", matrix);
-    let res = match_matrix_2(&matrix);
-    println!("{:?}", res);
+    fn part1(&self, matrix: &Matrix) -> Result<i32> {
+        Ok(match_matrix(matrix, "XMAS"))
+    }
+
+    fn part2(&self, matrix: &Matrix) -> Result<i32> {
+        Ok(match_matrix_2(matrix))
+    }
 }
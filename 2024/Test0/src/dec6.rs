@@ -1,65 +1,35 @@
 use std::cmp::PartialEq;
-use std::collections::HashSet;
-use crate::helpers::{read_matrix, Matrix, Vec2};
+use aoc_common::cycle::brent;
+use aoc_common::grid::Grid;
+use aoc_common::maze::{make_maze, Direction, Maze};
+use aoc_common::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
-#[derive(Debug)]
-pub struct Maze {
-    pub matrix: Matrix,
-    pub start: Vec2,
-}
-
-pub fn make_maze(mut matrix: Matrix, start_char: char) -> Maze {
-    for (row_idx, row) in matrix.data.iter_mut().enumerate() {
-        if let Some(pos) = row.find(start_char) {
-            let start = Vec2::new(pos as i32, row_idx as i32);
-            matrix.put(start, '.');
-            return Maze { matrix: matrix, start: start };
-        }
-    }
-    panic!("No start position found in maze: {matrix:?}");
-}
-
-
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
-pub enum Direction {
-    Up,
-    Right,
-    Down,
-    Left,
-}
+/// Position and direction of the guard. None once she escaped from the maze, which she then never comes back to.
+type Guard = Option<(Vec2, Direction)>;
 
-impl Direction {
-    pub fn turn_cw(&self) -> Self {
-        use Direction::*;
-        match *self {
-            Up => Right,
-            Right => Down,
-            Down => Left,
-            Left => Up,
-        }
-    }
-
-    pub fn turn_ccw(&self) -> Self {
-        use Direction::*;
-        match *self {
-            Up => Left,
-            Left => Down,
-            Down => Right,
-            Right => Up,
-        }
-    }
-
-    pub fn dir(&self) -> Vec2 {
-        use Direction::*;
-        match *self {
-            Up => return Vec2::new(0, -1),
-            Right => return Vec2::new(1, 0),
-            Down => return Vec2::new(0, 1),
-            Left => return Vec2::new(-1, 0),
+fn guard_step(matrix: &Matrix, guard: &Guard) -> Guard {
+    let (pos, dir) = (*guard)?;
+    let mut cur_dir = dir;
+    loop {
+        let next_pos = pos + cur_dir.dir();
+        match matrix.get(next_pos) {
+            None => return None,
+            Some('#') => {
+                cur_dir = cur_dir.turn_cw();
+                if cur_dir == dir {
+                    return Some((pos, dir));
+                }
+            },
+            Some(_) => return Some((next_pos, cur_dir)),
         }
     }
 }
 
+/// Returns true if the guard escapes, false if she ends up walking in a cycle.
 fn walk_maze(matrix: &mut Matrix, start: Vec2, start_dir: Direction, mark: bool) -> bool {
     match matrix.get(start) {
         None => { panic!("Start position {start:?} not inside maze: {matrix:?}"); }
@@ -67,84 +37,63 @@ fn walk_maze(matrix: &mut Matrix, start: Vec2, start_dir: Direction, mark: bool)
         _ => {}
     }
 
-    // Remember that we visited given position, going in the given direction.
-    // If we'll get to that again, that means we're in a cycle.
-    let mut visited = HashSet::<(Vec2, Direction)>::new();
+    // Escaping is a cycle too: the guard stays outside forever.
+    let cycle = brent(Some((start, start_dir)), |guard| guard_step(matrix, guard));
 
-    let mut pos = start;
-    let mut cur_dir = start_dir;
-    loop {
-        if !matrix.contains(pos) {
-            return true; // Escaped from the maze.
-        }
-        if mark {
+    // Walking through the steps before the cycle and then the cycle itself gets her back to the cycle start.
+    let mut guard = Some((start, start_dir));
+    for _ in 0..cycle.start + cycle.length {
+        if let (true, Some((pos, _))) = (mark, guard) {
             matrix.put(pos, 'X');
         }
-
-        if visited.contains(&(pos, cur_dir)) {
-            // We're in a cycle.
-            return false;
-        }
-        visited.insert((pos, cur_dir));
-
-        let save_dir = cur_dir;
-        loop {
-            let next_pos = pos + cur_dir.dir();
-            let next_cell = matrix.get(next_pos).unwrap_or('.');
-            if next_cell != '#' {
-                pos = next_pos;
-                break;
-            }
-            cur_dir = cur_dir.turn_cw();
-            if cur_dir == save_dir {
-                println!("Dude is trapped at: {:?}", pos);
-                return false;
-            }
-        }
+        guard = guard_step(matrix, &guard);
     }
+    guard.is_none()
 }
 
+/// Obstacles off the original path of the guard change nothing, so only the cells she walks through are tried.
+/// The walk with an obstacle is the same as the original one until she first bumps into it,
+/// so it is enough to continue from where she stood just before that.
 fn try_trap(matrix: &mut Matrix, start: Vec2, start_dir: Direction) -> i32 {
+    let mut visited = Grid::new(matrix.width, matrix.height, false);
+    visited[start] = true;
     let mut result = 0;
-    for x in 0 .. matrix.width {
-        for y in 0 .. matrix.height {
-            print!("{x} {y}");
-            let pos = Vec2::new(x as i32, y as i32);
-            if pos == start { println!(" start"); continue; }
-            let c = matrix.get(pos).unwrap();
-            if c != '.' { println!(" wall"); continue; }
-            matrix.put(pos, '#');
-            if walk_maze(matrix, start, start_dir, false) == false {
-                result += 1;
-                println!(" cycle");
-            } else {
-                println!(" ."); // escaped
-            }
-            matrix.put(pos, '.');
+    let mut guard = Some((start, start_dir));
+    while let Some((pos, dir)) = guard {
+        guard = guard_step(matrix, &guard);
+        let Some((next_pos, _)) = guard else { break; };
+        if visited[next_pos] { continue; }
+        visited[next_pos] = true;
+
+        matrix.put(next_pos, '#');
+        if !walk_maze(matrix, pos, dir, false) {
+            result += 1;
         }
+        matrix.put(next_pos, '.');
     }
     result
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec6() {
-    let matrix = read_matrix("dec6.ex.txt").expect("Could not load input.");
-    let mut maze = make_maze(matrix, '^');
-    walk_maze(&mut maze.matrix, maze.start, Direction::Up, true);
-    let result = maze.matrix.count('X');
-    println!("{}", result);
-    println!("{}", maze.matrix);
-    println!("");
-    println!("{}", result);
-}
+pub(crate) struct Dec6;
+
+impl Solver for Dec6 {
+    type Input = Maze;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Maze> {
+        let matrix = read_matrix_from_lines(lines.to_vec())?;
+        make_maze(matrix, '^')
+    }
 
-#[allow(dead_code)]
-pub(crate) fn dec6_2() {
-    let matrix = read_matrix("dec6.ex.txt").expect("Could not load input.");
-    let mut maze = make_maze(matrix, '^');
-    let result = try_trap(&mut maze.matrix, maze.start, Direction::Up);
-    //println!("{}", result);
-    //println!("{}", maze.matrix);
-    //println!("");
-    println!("{}", result);
+    fn part1(&self, maze: &Maze) -> Result<i32> {
+        let mut matrix = maze.matrix.clone();
+        walk_maze(&mut matrix, maze.start, Direction::Up, true);
+        Ok(matrix.count('X'))
+    }
+
+    fn part2(&self, maze: &Maze) -> Result<i32> {
+        let mut matrix = maze.matrix.clone();
+        Ok(try_trap(&mut matrix, maze.start, Direction::Up))
+    }
 }
@@ -1,6 +1,8 @@
 use itertools::Itertools;
 use regex::Regex;
-use crate::helpers::read_lines;
+use aoc_common::parse_error::{parse_token, ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 #[derive(Debug)]
 pub(crate) struct Task {
@@ -8,9 +10,7 @@ pub(crate) struct Task {
     numbers: Vec<i64>,
 }
 
-pub(crate) fn read_dec7_input(filename: &str) -> crate::helpers::Result<Vec<Task>> {
-    let lines = read_lines(filename)?;
-
+pub(crate) fn parse_dec7_input(lines: &[String]) -> ParseResult<Vec<Task>> {
     let mut tasks: Vec<Task> = Vec::new();
 
     let rule_regex = Regex::new(r"^(?<result>\d+): (?<numbers>\d+(?: \d+)*)$").unwrap();
@@ -20,11 +20,11 @@ pub(crate) fn read_dec7_input(filename: &str) -> crate::helpers::Result<Vec<Task
         }
 
         let Some(caps) = rule_regex.captures(line) else {
-            return Err(format!("Line idx={} (zero-based) {} does not match the rule regex.", idx, line).into());
+            return Err(ParseError::line(idx, line, "task like 190: 10 19"));
         };
         let task = Task {
-            result: caps["result"].parse::<i64>().unwrap(),
-            numbers: caps["numbers"].split(" ").map(|c| c.parse::<i64>().unwrap()).collect_vec(),
+            result: parse_token(idx, line, &caps["result"])?,
+            numbers: caps["numbers"].split(" ").map(|c| parse_token(idx, line, c)).try_collect()?,
         };
         tasks.push(task);
     }
@@ -125,16 +125,22 @@ fn dec7_count(tasks: &Vec<Task>, sec: bool) -> i64 {
     return result;
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec7() {
-    let tasks = read_dec7_input("dec7.in.txt").expect("Error loading input.");
-    let result = dec7_count(&tasks, false);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec7;
+
+impl Solver for Dec7 {
+    type Input = Vec<Task>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Task>> {
+        parse_dec7_input(lines)
+    }
+
+    fn part1(&self, tasks: &Vec<Task>) -> Result<i64> {
+        Ok(dec7_count(tasks, false))
+    }
 
-#[allow(dead_code)]
-pub(crate) fn dec7_2() {
-    let tasks = read_dec7_input("dec7.in.txt").expect("Error loading input.");
-    let result = dec7_count(&tasks, true);
-    println!("{:?}", result);
+    fn part2(&self, tasks: &Vec<Task>) -> Result<i64> {
+        Ok(dec7_count(tasks, true))
+    }
 }
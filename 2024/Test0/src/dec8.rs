@@ -1,6 +1,10 @@
 use itertools::Itertools;
 use std::collections::{HashMap, HashSet};
-use crate::helpers::{read_matrix, Matrix, Vec2};
+use aoc_common::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 fn get_antennas(matrix: &Matrix) -> HashMap<char, HashSet<Vec2>> {
     let mut antennas = HashMap::new();
@@ -59,18 +63,24 @@ fn process_antennas(matrix: &Matrix, antennas: &HashMap<char, HashSet<Vec2>>, li
     results.len() as i32
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec8() {
-    let matrix = read_matrix("dec8.in.txt").expect("Could not load input.");
-    let antennas = get_antennas(&matrix);
-    let result = process_antennas(&matrix, &antennas, true);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec8;
+
+impl Solver for Dec8 {
+    type Input = Matrix;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Matrix> {
+        read_matrix_from_lines(lines.to_vec())
+    }
 
-#[allow(dead_code)]
-pub(crate) fn dec8_2() {
-    let matrix = read_matrix("dec8.in.txt").expect("Could not load input.");
-    let antennas = get_antennas(&matrix);
-    let result = process_antennas(&matrix, &antennas, false);
-    println!("{:?}", result);
+    fn part1(&self, matrix: &Matrix) -> Result<i32> {
+        let antennas = get_antennas(matrix);
+        Ok(process_antennas(matrix, &antennas, true))
+    }
+
+    fn part2(&self, matrix: &Matrix) -> Result<i32> {
+        let antennas = get_antennas(matrix);
+        Ok(process_antennas(matrix, &antennas, false))
+    }
 }
@@ -1,6 +1,9 @@
 use std::cmp;
 use std::collections::{HashMap, HashSet};
-use crate::helpers::read_line;
+use aoc_common::basic_parsing::single_line;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 fn defrag_checksum(input: &mut str) -> i64 {
     let data = unsafe { input.as_bytes_mut() };
@@ -159,16 +162,23 @@ fn defrag_checksum2(input: &str) -> i64 {
     checksum
 }
 
-#[allow(dead_code)]
-pub(crate) fn dec9() {
-    let mut input = read_line("dec9.in.txt").expect("Could not load input.");
-    let result = defrag_checksum(input.as_mut_str());
-    println!("{:?}", result);
-}
+pub(crate) struct Dec9;
+
+impl Solver for Dec9 {
+    type Input = String;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<String> {
+        Ok(single_line(lines)?.to_string())
+    }
 
-#[allow(dead_code)]
-pub(crate) fn dec9_2() {
-    let mut input = read_line("dec9.in.txt").expect("Could not load input.");
-    let result = defrag_checksum2(input.as_mut_str());
-    println!("{:?}", result);
+    fn part1(&self, input: &String) -> Result<i64> {
+        let mut input = input.clone();
+        Ok(defrag_checksum(input.as_mut_str()))
+    }
+
+    fn part2(&self, input: &String) -> Result<i64> {
+        Ok(defrag_checksum2(input))
+    }
 }
@@ -0,0 +1,67 @@
+#![allow(unused_parens)]
+
+#[macro_use]
+extern crate maplit;
+
+mod dec4;
+mod dec5;
+mod dec6;
+mod dec7;
+mod dec8;
+mod dec9;
+mod dec10;
+mod dec11;
+mod dec12;
+mod dec13;
+mod dec14;
+mod dec15;
+mod dec16;
+mod dec17;
+mod dec18;
+mod dec19;
+mod dec20;
+mod dec21;
+mod dec22;
+mod dec23;
+mod dec24;
+mod dec25;
+mod playground;
+
+use aoc_common::runner::{Day, Year};
+
+/// All the solutions, in order.
+pub static DAYS: &[Day] = &[
+    Day { day: 4, solver: &dec4::Dec4 },
+    Day { day: 5, solver: &dec5::Dec5 },
+    Day { day: 6, solver: &dec6::Dec6 },
+    Day { day: 7, solver: &dec7::Dec7 },
+    Day { day: 8, solver: &dec8::Dec8 },
+    Day { day: 9, solver: &dec9::Dec9 },
+    Day { day: 10, solver: &dec10::Dec10 },
+    Day { day: 11, solver: &dec11::Dec11 },
+    Day { day: 12, solver: &dec12::Dec12 },
+    Day { day: 13, solver: &dec13::Dec13 },
+    Day { day: 14, solver: &dec14::Dec14 },
+    Day { day: 15, solver: &dec15::Dec15 },
+    Day { day: 16, solver: &dec16::Dec16 },
+    Day { day: 17, solver: &dec17::Dec17 },
+    Day { day: 18, solver: &dec18::Dec18 },
+    Day { day: 19, solver: &dec19::Dec19 },
+    Day { day: 20, solver: &dec20::Dec20 },
+    Day { day: 21, solver: &dec21::Dec21 },
+    Day { day: 22, solver: &dec22::Dec22 },
+    Day { day: 23, solver: &dec23::Dec23 },
+    Day { day: 24, solver: &dec24::Dec24 },
+    Day { day: 25, solver: &dec25::Dec25 },
+];
+
+/// Inputs are stored next to the sources of the crate.
+pub const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+/// Everything the runner needs to know about this year.
+pub static YEAR: Year = Year { days: DAYS, input_dir: INPUT_DIR, play: playground::play };
+
+#[cfg(test)]
+mod answers {
+    include!(concat!(env!("OUT_DIR"), "/answers_tests.rs"));
+}
@@ -1,34 +1,6 @@
-#![allow(unused_parens)]
+use std::process::ExitCode;
+use aoc_common::runner;
 
-#[macro_use]
-extern crate maplit;
-
-mod dec4;
-mod dec5;
-mod dec6;
-mod dec7;
-mod helpers;
-mod dec8;
-mod dec9;
-mod dec10;
-mod dec11;
-mod dec12;
-mod find_union;
-mod dec13;
-mod dec14;
-mod dec15;
-mod dec16;
-mod dec17;
-mod dec18;
-mod dec19;
-mod dec20;
-mod dec21;
-mod dec22;
-mod dec23;
-mod dec24;
-mod dec25;
-mod playground;
-
-fn main() {
-    playground::play()
+fn main() -> ExitCode {
+    runner::main(&aoc2024::YEAR)
 }
@@ -8,7 +8,8 @@ pub fn play() {
 
     {
         let mut r1 = &mut s;
-        let mut r2 = &mut s;
+        //let mut r2 = &mut s; // Second mutable borrow of s does not compile.
+        let r2 = String::from("world");
         //r1 = &mut s;
         //*r1 = s;
         mut_s(&mut r1, &r2);
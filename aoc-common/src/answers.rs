@@ -0,0 +1,65 @@
+//! Regression tests: every solver is run against the answers recorded in answers.txt.
+//! Each year's build.rs calls 'generate_tests', which writes one test per line, and the year includes them with
+//! ```text
+//! #[cfg(test)]
+//! mod answers {
+//!     include!(concat!(env!("OUT_DIR"), "/answers_tests.rs"));
+//! }
+//! ```
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use crate::runner::{read_input, Year};
+use crate::solver::solve;
+
+/// Generates one regression test per line of answers.txt, to be called from build.rs.
+/// Line format: `day part input answer [slow]`. Slow tests are ignored by default.
+/// The tests call 'check_answer' with the 'YEAR' of the crate.
+pub fn generate_tests() {
+    println!("cargo:rerun-if-changed=answers.txt");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let contents = fs::read_to_string("answers.txt").expect("Cannot read answers.txt");
+    let mut tests = String::new();
+    let mut names = Vec::new();
+    for (idx, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let pieces: Vec<&str> = line.split_whitespace().collect();
+        let (day, part, input, answer, slow) = match pieces.as_slice() {
+            [day, part, input, answer] => (day, part, input, answer, false),
+            [day, part, input, answer, "slow"] => (day, part, input, answer, true),
+            _ => panic!("answers.txt line {}: '{}' should be: day part input answer [slow]", idx + 1, line),
+        };
+        let day: u32 = day.parse().unwrap_or_else(|_| panic!("answers.txt line {}: bad day '{}'", idx + 1, day));
+        let part: u32 = part.parse().unwrap_or_else(|_| panic!("answers.txt line {}: bad part '{}'", idx + 1, part));
+
+        let ident: String = input.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '_' }).collect();
+        let name = format!("dec{}_part{}_{}", day, part, ident);
+        if names.contains(&name) {
+            panic!("answers.txt line {}: duplicate answer for day {} part {} input {}", idx + 1, day, part, input);
+        }
+
+        tests.push_str("#[test]\n");
+        if slow {
+            tests.push_str("#[ignore = \"slow\"]\n");
+        }
+        tests.push_str(&format!("fn {}() {{\n    aoc_common::answers::check_answer(&crate::YEAR, {}, {}, {:?}, {:?});\n}}\n\n", name, day, part, input, answer));
+        names.push(name);
+    }
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("answers_tests.rs"), tests).unwrap();
+}
+
+/// Solves given part of the day on 'decN.<input>.txt' and panics unless the answer is 'expected'.
+pub fn check_answer(year: &Year, day: u32, part: u32, input: &str, expected: &str) {
+    let entry = year.days.iter().find(|d| d.day == day).unwrap_or_else(|| panic!("No solver registered for day {}.", day));
+    let lines = read_input(year, entry, input).unwrap();
+    let answer = solve(entry.solver, &lines, input, part).unwrap();
+    assert_eq!(answer, expected, "Day {} part {} ({})", day, part, input);
+}
@@ -0,0 +1,299 @@
+use std::fs::read_to_string;
+use std::path::Path;
+use std::str::FromStr;
+use nom::bytes::complete::{tag, take_until, take_while1};
+use nom::combinator::{eof, rest, verify};
+use nom::{IResult, Parser};
+use crate::parse_error::{parse_token, ParseError, ParseResult};
+use crate::Result;
+
+pub fn read_lines<P: AsRef<Path>>(filename: P) -> Result<Vec<String>> {
+    let mut result = Vec::new();
+    let filename = filename.as_ref();
+    let contents = read_to_string(filename).map_err(|err| format!("Cannot read {}: {}", filename.display(), err))?;
+
+    for line in contents.lines() {
+        result.push(line.to_string())
+    }
+
+    Ok(result)
+}
+
+pub fn read_line(filename: &str) -> Result<String> {
+    let result = read_lines(filename)?;
+    if result.is_empty() {
+        return Err(From::from(format!("No lines in file: {}", filename)));
+    }
+    if result.len() > 1 {
+        return Err(From::from(format!("Expected only one line in file {}, but got: {}", filename, result.len())));
+    }
+
+    Ok(result.first().unwrap().clone())
+}
+
+/// Returns the only line of already read input.
+pub fn single_line(lines: &[String]) -> ParseResult<&str> {
+    match lines {
+        [line] => Ok(line.as_str()),
+        [] => Err(ParseError::end_of_input(lines, "one line")),
+        _ => Err(ParseError::line(1, &lines[1], "end of input after the first line")),
+    }
+}
+
+/// Parses space separated numbers of the line with given index.
+pub fn parse_nums<T: FromStr>(line_idx: usize, content: &str) -> ParseResult<Vec<T>> {
+    content.split(' ')
+        .map(|s| parse_token::<T>(line_idx, content, s))
+        .collect()
+}
+
+/// Separates input lines into two lists. Break is on first blank line.
+pub fn separate_by_blank(lines: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut s0: Vec<String> = Vec::new();
+    let mut s1: Vec<String> = Vec::new();
+    let mut first = true;
+    for line in lines {
+        if line.is_empty() {
+            first = false;
+            continue;
+        }
+        if first {
+            s0.push(line.clone());
+        } else {
+            s1.push(line.clone());
+        }
+    }
+
+    (s0, s1)
+}
+
+#[derive(Debug, Clone)]
+enum Piece {
+    Literal(String),
+    /// A field without a class takes everything up to the following literal, or the rest of the line.
+    /// A field with a class (like "[LR]" or "[a-z]") takes the longest run of matching characters.
+    Field { name: String, class: Option<Vec<(char, char)>> },
+}
+
+/// Compact pattern of a whole input line, e.g. "{x},{y},{z}" or "{dir:[LR]}{num}".
+/// Text outside of braces must match literally, "{{" and "}}" stand for literal braces.
+/// Each line is matched with nom, values of the fields are then parsed by 'Fields::get'.
+#[derive(Debug, Clone)]
+pub struct LinePattern {
+    pattern: String,
+    pieces: Vec<Piece>,
+}
+
+fn parse_class(class: &str, pattern: &str) -> Vec<(char, char)> {
+    let inner = class.strip_prefix('[').and_then(|c| c.strip_suffix(']'))
+        .filter(|c| !c.is_empty())
+        .unwrap_or_else(|| panic!("Class '{}' of pattern \"{}\" is not like [a-z]", class, pattern));
+    let chars: Vec<char> = inner.chars().collect();
+    let mut ranges = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' {
+            ranges.push((chars[i], chars[i + 2]));
+            i += 3;
+        } else {
+            ranges.push((chars[i], chars[i]));
+            i += 1;
+        }
+    }
+    ranges
+}
+
+impl LinePattern {
+    /// Panics if the pattern itself is malformed, as it comes from the code and not from the input.
+    pub fn new(pattern: &str) -> Self {
+        let mut pieces: Vec<Piece> = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => { chars.next(); literal.push('{'); },
+                '}' if chars.peek() == Some(&'}') => { chars.next(); literal.push('}'); },
+                '{' => {
+                    let field: String = chars.by_ref().take_while(|&c| c != '}').collect();
+                    let (name, class) = match field.split_once(':') {
+                        Some((name, class)) => (name, Some(parse_class(class, pattern))),
+                        None => (field.as_str(), None),
+                    };
+                    if name.is_empty() {
+                        panic!("Pattern \"{}\" has a field without name", pattern);
+                    }
+                    if let Some(Piece::Field { name: prev, class: None }) = pieces.last() {
+                        if literal.is_empty() {
+                            panic!("Field {} of pattern \"{}\" needs a class, as another field follows it", prev, pattern);
+                        }
+                    }
+                    if !literal.is_empty() {
+                        pieces.push(Piece::Literal(std::mem::take(&mut literal)));
+                    }
+                    pieces.push(Piece::Field { name: name.to_string(), class });
+                },
+                '}' => panic!("Pattern \"{}\" has an unmatched '}}'", pattern),
+                _ => literal.push(c),
+            }
+        }
+        if !literal.is_empty() {
+            pieces.push(Piece::Literal(literal));
+        }
+
+        LinePattern { pattern: pattern.to_string(), pieces }
+    }
+
+    fn expected(&self, i: usize) -> String {
+        match (&self.pieces[i], self.pieces.get(i + 1)) {
+            (Piece::Literal(literal), _) => format!("'{}' as in \"{}\"", literal, self.pattern),
+            (Piece::Field { name, class: None }, Some(Piece::Literal(next))) =>
+                format!("{} followed by '{}' as in \"{}\"", name, next, self.pattern),
+            (Piece::Field { name, .. }, _) => format!("{} as in \"{}\"", name, self.pattern),
+        }
+    }
+
+    /// Matches the whole line. On failure points at the first piece that did not match.
+    pub fn parse<'a>(&'a self, line_idx: usize, line: &'a str) -> ParseResult<Fields<'a>> {
+        let mut values = Vec::new();
+        let mut remaining = line;
+        for (i, piece) in self.pieces.iter().enumerate() {
+            let result: IResult<&str, &str> = match piece {
+                Piece::Literal(literal) => tag(literal.as_str()).parse(remaining),
+                Piece::Field { class: Some(class), .. } =>
+                    take_while1(|c: char| class.iter().any(|&(from, to)| from <= c && c <= to)).parse(remaining),
+                Piece::Field { class: None, .. } => match self.pieces.get(i + 1) {
+                    Some(Piece::Literal(next)) => verify(take_until(next.as_str()), |s: &str| !s.is_empty()).parse(remaining),
+                    _ => verify(rest, |s: &str| !s.is_empty()).parse(remaining),
+                },
+            };
+            let (left, value) = result.map_err(|err| ParseError::from_nom(line_idx, line, err, self.expected(i)))?;
+            if let Piece::Field { name, .. } = piece {
+                values.push((name.as_str(), value));
+            }
+            remaining = left;
+        }
+        let end: IResult<&str, &str> = eof.parse(remaining);
+        end.map_err(|err| ParseError::from_nom(line_idx, line, err, "end of line"))?;
+
+        Ok(Fields { line_idx, line, values })
+    }
+}
+
+/// Values of the fields of one line matched by 'LinePattern'.
+#[derive(Debug, Clone)]
+pub struct Fields<'a> {
+    line_idx: usize,
+    line: &'a str,
+    values: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Fields<'a> {
+    /// Raw text of the field. Panics if the pattern has no such field.
+    pub fn str(&self, name: &str) -> &'a str {
+        self.values.iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("Pattern has no field {}", name))
+    }
+
+    /// Parses the field as T. On failure points at the field's value.
+    pub fn get<T: FromStr>(&self, name: &str) -> ParseResult<T> {
+        parse_token(self.line_idx, self.line, self.str(name))
+    }
+}
+
+/// Type built from a single input line. Usually implemented by 'line_struct!'.
+pub trait FromLine: Sized {
+    const PATTERN: &'static str;
+
+    fn from_fields(fields: &Fields) -> ParseResult<Self>;
+}
+
+/// Parses every line with 'T::PATTERN'.
+pub fn parse_lines<T: FromLine>(lines: &[String]) -> ParseResult<Vec<T>> {
+    parse_lines_with(lines, T::PATTERN, T::from_fields)
+}
+
+/// Parses every line with given pattern, building values by 'build'. For types defined elsewhere, like 'Vec2'.
+pub fn parse_lines_with<T>(lines: &[String], pattern: &str, build: impl Fn(&Fields) -> ParseResult<T>) -> ParseResult<Vec<T>> {
+    let pattern = LinePattern::new(pattern);
+    lines.iter().enumerate()
+        .map(|(idx, line)| build(&pattern.parse(idx, line)?))
+        .collect()
+}
+
+/// Defines a struct parsed from a line by given pattern. Fields of the pattern are named as fields of the struct
+/// and their types must implement FromStr:
+/// ```text
+/// line_struct! {
+///     "{dir:[LR]}{num}"
+///     #[derive(Debug, Clone, Copy)]
+///     pub(crate) struct Input {
+///         dir: char,
+///         num: i64,
+///     }
+/// }
+/// ```
+/// Then 'parse_lines::<Input>(lines)' parses the whole input.
+#[macro_export]
+macro_rules! line_struct {
+    (
+        $pattern:literal
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident {
+            $($field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        $vis struct $name {
+            $($field_vis $field: $ty),*
+        }
+
+        impl $crate::basic_parsing::FromLine for $name {
+            const PATTERN: &'static str = $pattern;
+
+            fn from_fields(fields: &$crate::basic_parsing::Fields) -> $crate::parse_error::ParseResult<Self> {
+                Ok(Self { $($field: fields.get(stringify!($field))?),* })
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classes() {
+        let pattern = LinePattern::new("{dir:[LR]}{num}");
+        let fields = pattern.parse(0, "R12").unwrap();
+        assert_eq!(fields.str("dir"), "R");
+        assert_eq!(fields.get::<i64>("num").unwrap(), 12);
+
+        let pattern = LinePattern::new("{name:[a-z0-9]}: {value:[01]}");
+        let fields = pattern.parse(0, "x07: 1").unwrap();
+        assert_eq!(fields.str("name"), "x07");
+        assert_eq!(fields.str("value"), "1");
+    }
+
+    #[test]
+    fn escaped_braces() {
+        let pattern = LinePattern::new("{{{x}}} {{}}");
+        let fields = pattern.parse(0, "{42} {}").unwrap();
+        assert_eq!(fields.get::<u32>("x").unwrap(), 42);
+        assert!(pattern.parse(0, "42 {}").is_err());
+    }
+
+    #[test]
+    fn error_columns() {
+        let column = |pattern: &str, line: &str| LinePattern::new(pattern).parse(3, line).unwrap_err().column;
+        assert_eq!(column("{dir:[LR]}{num}", "X12"), 0);
+        assert_eq!(column("p={x:[0-9]},v", "p=3;v"), 3);
+        assert_eq!(column("{x},{y}", "1;2"), 0);
+        assert_eq!(column("{a:[a-z]}", "ab1"), 2);
+
+        let pattern = LinePattern::new("{dir:[LR]}{num}");
+        let err = pattern.parse(3, "R12x").unwrap().get::<i64>("num").unwrap_err();
+        assert_eq!((err.line_idx, err.column, err.expected.as_str()), (3, 1, "i64"));
+    }
+}
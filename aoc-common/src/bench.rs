@@ -0,0 +1,33 @@
+use std::hint::black_box;
+use criterion::Criterion;
+use crate::runner::{read_input, Year};
+
+/// Registers parse, part1 and part2 of every day of the year as Criterion benchmarks, named e.g. "dec9/part2".
+/// Puzzle inputs (dec*.in.txt) are used. Days whose input is missing or cannot be parsed are skipped.
+pub fn bench_year(c: &mut Criterion, year: &Year) {
+    for day in year.days {
+        let lines = match read_input(year, day, "in") {
+            Ok(lines) => lines,
+            Err(err) => {
+                eprintln!("Skipping day {}: {}", day.day, err);
+                continue;
+            },
+        };
+        let parsed = match day.solver.parse_any(&lines, "in") {
+            Ok(parsed) => parsed,
+            Err(err) => {
+                eprintln!("Skipping day {}: {}", day.day, err);
+                continue;
+            },
+        };
+
+        let mut group = c.benchmark_group(format!("dec{}", day.day));
+        // Some parts take seconds, the default 100 samples would take ages.
+        group.sample_size(10);
+        group.bench_function("parse", |b| b.iter(|| day.solver.parse_any(black_box(&lines), "in")));
+        for part in 1..=day.solver.parts() {
+            group.bench_function(format!("part{}", part), |b| b.iter(|| day.solver.solve_any(black_box(parsed.as_ref()), part)));
+        }
+        group.finish();
+    }
+}
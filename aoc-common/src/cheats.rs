@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use itertools::Itertools;
+use crate::grid::{diamond, Grid};
+use crate::matrix::Matrix;
+use crate::search::bfs;
+use crate::vec2::Vec2;
+
+/// Jump from track position 'from' to track position 'to', ignoring walls on the way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cheat {
+    pub from: Vec2,
+    pub to: Vec2,
+    /// Steps the jump takes, the Manhattan distance of the two positions.
+    pub length: usize,
+    /// How much shorter the race gets, compared to the best one without cheating.
+    pub saving: usize,
+}
+
+/// Race track: everything in the matrix that is not a '#' wall.
+/// Knows distances of each track position from the start and to the end, so each cheat is checked in O(1).
+#[derive(Debug, Clone)]
+pub struct RaceTrack {
+    pub from_start: Grid<Option<usize>>,
+    pub to_end: Grid<Option<usize>>,
+    /// Length of the best race without cheating.
+    pub best: usize,
+}
+
+fn track_distances(matrix: &Matrix, from: Vec2) -> Grid<Option<usize>> {
+    let successors = |&pos: &Vec2| matrix.neighbors4(pos).filter(|&next_pos| matrix.get(next_pos) != Some('#')).collect_vec();
+    let mut result = Grid::new(matrix.width, matrix.height, None);
+    for (pos, dist) in bfs([from], successors, |_| false).dist {
+        result[pos] = Some(dist);
+    }
+    result
+}
+
+impl RaceTrack {
+    /// None if the end cannot be reached from the start.
+    pub fn new(matrix: &Matrix, start: Vec2, end: Vec2) -> Option<Self> {
+        let from_start = track_distances(matrix, start);
+        let to_end = track_distances(matrix, end);
+        let best = from_start[end]?;
+        Some(RaceTrack { from_start, to_end, best })
+    }
+
+    /// Calls 'f' for every cheat of length up to 'max_len' saving at least 'min_saving' steps.
+    /// Only the positions in the diamond of radius 'max_len' around each track position are checked.
+    pub fn for_each_cheat(&self, max_len: usize, min_saving: usize, mut f: impl FnMut(Cheat)) {
+        let offsets = diamond(max_len);
+        for (from, &from_dist) in self.from_start.iter() {
+            let Some(from_dist) = from_dist else { continue; };
+            if from_dist + min_saving > self.best { continue; }
+            for &offset in &offsets {
+                let to = from + offset;
+                let Some(&Some(to_dist)) = self.to_end.get(to) else { continue; };
+                let length = offset.manhattan() as usize;
+                let total = from_dist + length + to_dist;
+                if total + min_saving <= self.best {
+                    f(Cheat { from, to, length, saving: self.best - total });
+                }
+            }
+        }
+    }
+
+    pub fn cheats(&self, max_len: usize, min_saving: usize) -> Vec<Cheat> {
+        let mut result = Vec::new();
+        self.for_each_cheat(max_len, min_saving, |cheat| result.push(cheat));
+        result
+    }
+
+    pub fn count_cheats(&self, max_len: usize, min_saving: usize) -> usize {
+        let mut count = 0;
+        self.for_each_cheat(max_len, min_saving, |_| count += 1);
+        count
+    }
+
+    /// Number of cheats saving each number of steps.
+    pub fn histogram(&self, max_len: usize, min_saving: usize) -> BTreeMap<usize, usize> {
+        let mut result = BTreeMap::new();
+        self.for_each_cheat(max_len, min_saving, |cheat| *result.entry(cheat.saving).or_default() += 1);
+        result
+    }
+}
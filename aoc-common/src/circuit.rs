@@ -0,0 +1,420 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use crate::basic_parsing::{separate_by_blank, LinePattern};
+use crate::parse_error::{ParseError, ParseResult};
+use crate::topo_sort::{topo_sort, CycleError};
+use crate::Result;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub enum Op {
+    And,
+    Or,
+    Xor,
+}
+
+impl Op {
+    pub fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Op::And => a && b,
+            Op::Or => a || b,
+            Op::Xor => a != b,
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Op::And => "AND",
+            Op::Or => "OR",
+            Op::Xor => "XOR",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Gate with two input wires. Its output wire is the key in 'Circuit::gates'.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Gate {
+    pub a: String,
+    pub b: String,
+    pub op: Op,
+}
+
+impl Gate {
+    /// The input other than 'wire', None if 'wire' is not an input of the gate.
+    fn other_input(&self, wire: &str) -> Option<&str> {
+        if self.a == wire {
+            Some(&self.b)
+        } else if self.b == wire {
+            Some(&self.a)
+        } else {
+            None
+        }
+    }
+}
+
+/// Boolean circuit of wires with initial values and gates, e.g.:
+/// ```text
+/// x00: 1
+/// y00: 0
+///
+/// x00 AND y00 -> z00
+/// ```
+/// Numbers are spread over wires with a common prefix, one bit each, "x00" being the least significant bit of x.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Circuit {
+    /// Initial values of the input wires.
+    pub states: BTreeMap<String, bool>,
+    /// Gates by their output wires.
+    pub gates: BTreeMap<String, Gate>,
+}
+
+/// Name of the wire with given bit of a number, e.g. "z07".
+pub fn wire(prefix: char, bit: usize) -> String {
+    format!("{}{:02}", prefix, bit)
+}
+
+impl Circuit {
+    pub fn parse(lines: &[String]) -> ParseResult<Circuit> {
+        let (state_lines, gate_lines) = separate_by_blank(lines);
+        let mut circuit = Circuit { states: BTreeMap::new(), gates: BTreeMap::new() };
+
+        let state_pattern = LinePattern::new("{name:[a-z0-9]}: {value:[01]}");
+        for (idx, line) in state_lines.iter().enumerate() {
+            let fields = state_pattern.parse(idx, line)?;
+            circuit.states.insert(fields.str("name").to_string(), fields.str("value") == "1");
+        }
+
+        // Gates follow the states and the blank line.
+        let first_gate_idx = state_lines.len() + 1;
+        let gate_pattern = LinePattern::new("{a:[a-z0-9]} {op:[A-Z]} {b:[a-z0-9]} -> {out:[a-z0-9]}");
+        for (idx, line) in gate_lines.iter().enumerate() {
+            let idx = first_gate_idx + idx;
+            let fields = gate_pattern.parse(idx, line)?;
+            let op = match fields.str("op") {
+                "AND" => Op::And,
+                "OR" => Op::Or,
+                "XOR" => Op::Xor,
+                other => return Err(ParseError::at(idx, line, other, "AND, OR or XOR")),
+            };
+            let out = fields.str("out");
+            if circuit.states.contains_key(out) || circuit.gates.contains_key(out) {
+                return Err(ParseError::at(idx, line, out, "wire without another source"));
+            }
+            circuit.gates.insert(out.to_string(), Gate { a: fields.str("a").to_string(), b: fields.str("b").to_string(), op });
+        }
+
+        Ok(circuit)
+    }
+
+    /// Output wires of the gates, each after the outputs of the gates it depends on.
+    pub fn order(&self) -> std::result::Result<Vec<String>, CycleError<String>> {
+        let edges = self.gates.iter().flat_map(|(out, gate)| [(gate.a.clone(), out.clone()), (gate.b.clone(), out.clone())]);
+        let order = topo_sort(self.gates.keys().cloned(), edges)?;
+        Ok(order.into_iter().filter(|wire| self.gates.contains_key(wire)).collect())
+    }
+
+    /// Values of all the wires, given values of the input wires.
+    /// Fails if the gates make a cycle or some gate has an input without a value.
+    pub fn evaluate(&self, inputs: &BTreeMap<String, bool>) -> Result<BTreeMap<String, bool>> {
+        self.evaluate_in(&self.order()?, inputs)
+    }
+
+    /// 'evaluate' with the 'order' of the gates computed already.
+    fn evaluate_in(&self, order: &[String], inputs: &BTreeMap<String, bool>) -> Result<BTreeMap<String, bool>> {
+        let mut values = inputs.clone();
+        for out in order {
+            let gate = &self.gates[out];
+            let input = |wire: &str| values.get(wire).copied().ok_or_else(|| format!("Wire {} of gate {} has no value.", wire, out));
+            let value = gate.op.apply(input(&gate.a)?, input(&gate.b)?);
+            values.insert(out.clone(), value);
+        }
+        Ok(values)
+    }
+
+    /// Number of bits of input x.
+    pub fn input_bits(&self) -> usize {
+        (0..).take_while(|&bit| self.states.contains_key(&wire('x', bit))).count()
+    }
+
+    /// The number on wires starting with 'prefix'. Missing bits are zeros.
+    pub fn read_number(values: &BTreeMap<String, bool>, prefix: char) -> u64 {
+        (0..64).filter(|&bit| values.get(&wire(prefix, bit)) == Some(&true)).map(|bit| 1 << bit).sum()
+    }
+
+    /// Sets 'bits' wires starting with 'prefix' to 'value'.
+    pub fn write_number(values: &mut BTreeMap<String, bool>, prefix: char, bits: usize, value: u64) {
+        for bit in 0..bits {
+            values.insert(wire(prefix, bit), value & (1 << bit) != 0);
+        }
+    }
+
+    /// Value of z with the initial values of the wires.
+    pub fn output(&self) -> Result<u64> {
+        Ok(Self::read_number(&self.evaluate(&self.states)?, 'z'))
+    }
+
+    /// Value of z with given x and y instead of the initial ones.
+    pub fn compute(&self, x: u64, y: u64) -> Result<u64> {
+        self.compute_in(&self.order()?, x, y)
+    }
+
+    fn compute_in(&self, order: &[String], x: u64, y: u64) -> Result<u64> {
+        let mut inputs = self.states.clone();
+        let bits = self.input_bits();
+        Self::write_number(&mut inputs, 'x', bits, x);
+        Self::write_number(&mut inputs, 'y', bits, y);
+        Ok(Self::read_number(&self.evaluate_in(order, &inputs)?, 'z'))
+    }
+
+    /// Swaps output wires of two gates. Returns false (and changes nothing) if one of them is not a gate output.
+    pub fn swap_outputs(&mut self, a: &str, b: &str) -> bool {
+        if a == b || !self.gates.contains_key(a) || !self.gates.contains_key(b) {
+            return false;
+        }
+        let gate_a = self.gates.remove(a).unwrap();
+        let gate_b = self.gates.insert(b.to_string(), gate_a).unwrap();
+        self.gates.insert(a.to_string(), gate_b);
+        true
+    }
+
+    /// Output of the gate with given operation and inputs (in any order).
+    fn find(&self, op: Op, a: &str, b: &str) -> Option<String> {
+        self.gates.iter().find(|(_, gate)| gate.op == op && gate.other_input(a) == Some(b)).map(|(out, _)| out.clone())
+    }
+
+    /// There is no 'a' 'op' 'b' gate. For every gate with the right operation and one of the inputs,
+    /// its other input may be the one that got swapped (if both are gate outputs, inputs cannot be swapped).
+    fn misconnected(&self, bit: usize, op: Op, a: &str, b: &str) -> AdderFault {
+        let mut fault = AdderFault { bit, problem: format!("no {} {} {} gate", a, op, b), swaps: Vec::new() };
+        let mut near = Vec::new();
+        for (out, gate) in &self.gates {
+            if gate.op != op {
+                continue;
+            }
+            let (wrong, other) = match (gate.other_input(a), gate.other_input(b)) {
+                (Some(other), _) => (b, other),
+                (_, Some(other)) => (a, other),
+                _ => continue,
+            };
+            near.push(out.as_str());
+            let swap = (wrong.to_string(), other.to_string());
+            if self.gates.contains_key(wrong) && self.gates.contains_key(other) && !fault.swaps.contains(&swap) {
+                fault.swaps.push(swap);
+            }
+        }
+        if !near.is_empty() {
+            fault.problem = format!("{}, just one of the inputs goes to {}", fault.problem, near.join(", "));
+        }
+        fault
+    }
+
+    /// Checks bit by bit, from the least significant one, that the circuit is a ripple-carry adder z = x + y:
+    /// ```text
+    /// half_sum = x XOR y, half_carry = x AND y    (z00 = half_sum and carry = half_carry for bit 0)
+    /// z = half_sum XOR carry_in
+    /// carry_out = half_carry OR (half_sum AND carry_in)
+    /// ```
+    /// and the last carry is the highest bit of z. Reports the first gate that is not where it should be.
+    pub fn check_adder(&self) -> std::result::Result<(), AdderFault> {
+        let bits = self.input_bits();
+        let mut carry: Option<String> = None;
+        for bit in 0..bits {
+            let (x, y, z) = (wire('x', bit), wire('y', bit), wire('z', bit));
+            let half_sum = self.find(Op::Xor, &x, &y).ok_or_else(|| self.misconnected(bit, Op::Xor, &x, &y))?;
+            let half_carry = self.find(Op::And, &x, &y).ok_or_else(|| self.misconnected(bit, Op::And, &x, &y))?;
+            let Some(carry_in) = carry else {
+                // Bit 0 is just a half adder.
+                check_output(bit, &half_sum, &z)?;
+                carry = Some(half_carry);
+                continue;
+            };
+
+            let sum = self.find(Op::Xor, &half_sum, &carry_in).ok_or_else(|| self.misconnected(bit, Op::Xor, &half_sum, &carry_in))?;
+            check_output(bit, &sum, &z)?;
+            let carried = self.find(Op::And, &half_sum, &carry_in).ok_or_else(|| self.misconnected(bit, Op::And, &half_sum, &carry_in))?;
+            let carry_out = self.find(Op::Or, &half_carry, &carried).ok_or_else(|| self.misconnected(bit, Op::Or, &half_carry, &carried))?;
+            carry = Some(carry_out);
+        }
+        match carry {
+            Some(carry) => check_output(bits, &carry, &wire('z', bits)),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks that z = x + y on the edge cases (zeros, all ones, single bits and their carries) and on pseudo-random inputs.
+    /// Fails with the lowest wrong bit of the first wrong sum, or if the circuit cannot be evaluated.
+    pub fn check_sums(&self) -> std::result::Result<(), AdderFault> {
+        let bits = self.input_bits().min(63);
+        let mask = (1u64 << bits) - 1;
+        let mut cases = vec![(0, 0), (mask, 0), (0, mask), (mask, mask), (mask, 1)];
+        for bit in 0..bits {
+            cases.extend([(1 << bit, 0), (0, 1 << bit), (1 << bit, 1 << bit)]);
+        }
+        // Splitmix64, so that the check does not need a random number crate and is repeatable.
+        let mut seed = 0x9e37_79b9_7f4a_7c15u64;
+        let mut random = || {
+            seed = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            (z ^ (z >> 31)) & mask
+        };
+        for _ in 0..100 {
+            cases.push((random(), random()));
+        }
+
+        let unusable = |err: Box<dyn Error>| AdderFault { bit: 0, problem: err.to_string(), swaps: Vec::new() };
+        let order = self.order().map_err(|err| unusable(err.into()))?;
+        for (x, y) in cases {
+            let z = self.compute_in(&order, x, y).map_err(unusable)?;
+            let wrong = z ^ (x + y);
+            if wrong != 0 {
+                let problem = format!("{} + {} gives {}", x, y, z);
+                return Err(AdderFault { bit: wrong.trailing_zeros() as usize, problem, swaps: Vec::new() });
+            }
+        }
+        Ok(())
+    }
+
+    /// Fewest swaps of gate outputs (up to 'max_swaps') that make the circuit pass 'check_adder' and 'check_sums',
+    /// in the order they were made. A heuristic: only the swaps the faults of 'check_adder' suggest are tried,
+    /// by iterative deepening, and a swap must not move the first fault to a lower bit.
+    /// Fails with the first fault of the circuit if no such swaps are found.
+    pub fn fix_adder(&self, max_swaps: usize) -> std::result::Result<Vec<(String, String)>, AdderFault> {
+        let fault = match self.check_adder().and_then(|()| self.check_sums()) {
+            Ok(()) => return Ok(Vec::new()),
+            Err(fault) => fault,
+        };
+        for limit in 1..=max_swaps {
+            let mut circuit = self.clone();
+            let mut swaps = Vec::new();
+            if circuit.search_swaps(&mut swaps, limit) {
+                return Ok(swaps);
+            }
+        }
+        Err(fault)
+    }
+
+    /// Depth-first part of 'fix_adder'. Leaves the circuit as it was if it fails.
+    fn search_swaps(&mut self, swaps: &mut Vec<(String, String)>, limit: usize) -> bool {
+        let fault = match self.check_adder().and_then(|()| self.check_sums()) {
+            Ok(()) => return true,
+            Err(fault) => fault,
+        };
+        if swaps.len() == limit {
+            return false;
+        }
+        for (a, b) in fault.swaps {
+            if !self.swap_outputs(&a, &b) {
+                continue;
+            }
+            let progress = match self.check_adder() {
+                Ok(()) => true,
+                Err(next) => next.bit >= fault.bit,
+            };
+            swaps.push((a.clone(), b.clone()));
+            if progress && self.search_swaps(swaps, limit) {
+                return true;
+            }
+            swaps.pop();
+            self.swap_outputs(&a, &b);
+        }
+        false
+    }
+}
+
+fn check_output(bit: usize, actual: &str, expected: &str) -> std::result::Result<(), AdderFault> {
+    if actual == expected {
+        return Ok(());
+    }
+    let problem = format!("{} is computed on {}", expected, actual);
+    Err(AdderFault { bit, problem, swaps: vec![(actual.to_string(), expected.to_string())] })
+}
+
+/// A place where the circuit is not a ripple-carry adder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdderFault {
+    pub bit: usize,
+    pub problem: String,
+    /// Pairs of output wires whose swap may fix this bit, empty if there is no clue.
+    pub swaps: Vec<(String, String)>,
+}
+
+impl fmt::Display for AdderFault {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Bit {}: {}", self.bit, self.problem)?;
+        if !self.swaps.is_empty() {
+            let swaps = self.swaps.iter().map(|(a, b)| format!("{} and {}", a, b)).collect::<Vec<_>>();
+            write!(f, " (swap {}?)", swaps.join(" or "))?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for AdderFault {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ripple-carry adder of two 4-bit numbers. Half sums are 'sNN', half carries 'hNN', carried bits 'tNN', carries 'cNN'.
+    fn adder() -> Circuit {
+        let mut lines: Vec<String> = (0..4).flat_map(|bit| [format!("x{:02}: 0", bit), format!("y{:02}: 0", bit)]).collect();
+        lines.push(String::new());
+        lines.push("x00 XOR y00 -> z00".to_string());
+        lines.push("x00 AND y00 -> c00".to_string());
+        for bit in 1..4 {
+            let carry_out = if bit == 3 { "z04".to_string() } else { format!("c{:02}", bit) };
+            lines.extend([
+                format!("x{0:02} XOR y{0:02} -> s{0:02}", bit),
+                format!("x{0:02} AND y{0:02} -> h{0:02}", bit),
+                format!("s{0:02} XOR c{1:02} -> z{0:02}", bit, bit - 1),
+                format!("s{0:02} AND c{1:02} -> t{0:02}", bit, bit - 1),
+                format!("h{0:02} OR t{0:02} -> {1}", bit, carry_out),
+            ]);
+        }
+        Circuit::parse(&lines).unwrap()
+    }
+
+    #[test]
+    fn correct_adder() {
+        let circuit = adder();
+        assert_eq!(circuit.input_bits(), 4);
+        assert_eq!(circuit.compute(15, 9).unwrap(), 24);
+        assert_eq!(circuit.check_adder(), Ok(()));
+        assert_eq!(circuit.check_sums(), Ok(()));
+        assert_eq!(circuit.fix_adder(2), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn two_swaps() {
+        let mut circuit = adder();
+        assert!(circuit.swap_outputs("z01", "c01"));
+        assert!(circuit.swap_outputs("s03", "h03"));
+        assert!(!circuit.swap_outputs("z01", "x00"));
+        assert!(circuit.check_sums().is_err());
+
+        let fault = circuit.check_adder().unwrap_err();
+        assert_eq!(fault.bit, 1);
+        assert_eq!(fault.to_string(), "Bit 1: z01 is computed on c01 (swap c01 and z01?)");
+
+        // One swap is not enough.
+        assert_eq!(circuit.fix_adder(1).unwrap_err(), fault);
+
+        let mut swaps = circuit.fix_adder(2).unwrap();
+        for (a, b) in &mut swaps {
+            if a > b {
+                std::mem::swap(a, b);
+            }
+        }
+        assert_eq!(swaps, vec![("c01".to_string(), "z01".to_string()), ("h03".to_string(), "s03".to_string())]);
+
+        for (a, b) in &swaps {
+            circuit.swap_outputs(a, b);
+        }
+        assert_eq!(circuit.check_sums(), Ok(()));
+    }
+}
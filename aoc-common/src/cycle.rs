@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Where an iterated simulation 'state = step(state)' starts repeating itself.
+/// States at steps 'start + k * length + i' are all equal, for any k.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Number of steps before the first state that repeats.
+    pub start: usize,
+    /// Number of steps it takes to get back to the same state.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Step before 'start + length' that has the same state as step 'n'.
+    pub fn equivalent_step(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+        self.start + (n - self.start) % self.length
+    }
+}
+
+/// Brent's cycle detection. Needs no memory, but evaluates 'step' a few times more than 'simulate_until_cycle'.
+pub fn brent<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    // Find the length: the hare runs ahead, the tortoise teleports to it at every power of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    // Find the start: the hare gets a head start of one cycle, they meet where the cycle starts.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Floyd's cycle detection (tortoise and hare). Needs no memory, but is usually slower than 'brent'.
+pub fn floyd<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let half = step(&hare);
+        hare = step(&half);
+    }
+
+    let mut start = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// States of a simulation up to the point where it repeats itself.
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    /// States at steps 0 .. start + length.
+    pub states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// State at step 'n', however large.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.equivalent_step(n)]
+    }
+}
+
+/// Runs the simulation, remembering every state, until a state repeats. Evaluates 'step' only once per state.
+pub fn simulate_until_cycle<S: Hash + Eq + Clone>(initial: S, mut step: impl FnMut(&S) -> S) -> History<S> {
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut states = Vec::new();
+    let mut state = initial;
+    loop {
+        if let Some(&start) = seen.get(&state) {
+            let cycle = Cycle { start, length: states.len() - start };
+            return History { cycle, states };
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/// State at step 'n', however large. Finds the cycle by 'brent' and then simulates only up to the equivalent step.
+pub fn state_at<S: PartialEq + Clone>(initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+    let cycle = brent(initial.clone(), &mut step);
+    let mut state = initial;
+    for _ in 0..cycle.equivalent_step(n) {
+        state = step(&state);
+    }
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 3, 9, 81, 561, ... repeats 81 after 20 more steps.
+    fn square(x: &u64) -> u64 {
+        x * x % 1000
+    }
+
+    #[test]
+    fn detectors_agree() {
+        let expected = Cycle { start: 2, length: 20 };
+        assert_eq!(brent(3, square), expected);
+        assert_eq!(floyd(3, square), expected);
+        let history = simulate_until_cycle(3, square);
+        assert_eq!(history.cycle, expected);
+        assert_eq!(history.states.len(), 22);
+
+        // No tail, a fixed point.
+        assert_eq!(brent(1, square), Cycle { start: 0, length: 1 });
+        assert_eq!(floyd(1, square), Cycle { start: 0, length: 1 });
+        assert_eq!(simulate_until_cycle(1, square).cycle, Cycle { start: 0, length: 1 });
+    }
+
+    #[test]
+    fn states_far_ahead() {
+        let history = simulate_until_cycle(3, square);
+        for n in [0, 1, 2, 21, 22, 1000] {
+            let simulated = (0..n).fold(3, |x, _| square(&x));
+            assert_eq!(state_at(3, square, n), simulated, "step {}", n);
+            assert_eq!(*history.state_at(n), simulated, "step {}", n);
+        }
+        assert_eq!(state_at(3, square, 1000), 921);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use crate::interner::Interner;
+use crate::topo_sort::{kahn, CycleError};
+use crate::Result;
+
+/// Directed graph. Nodes are values of T, interned to dense ids in order of appearance.
+#[derive(Debug, Clone)]
+pub struct Digraph<T: Clone + Eq + Hash> {
+    nodes: Interner<T>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl<T: Clone + Eq + Hash> Default for Digraph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Eq + Hash> Digraph<T> {
+    pub fn new() -> Self {
+        Self { nodes: Interner::new(), successors: Vec::new() }
+    }
+
+    /// Id of the node, a new one if it is not in the graph yet.
+    pub fn add_node(&mut self, u: T) -> usize {
+        let id = self.nodes.intern(u);
+        if id == self.successors.len() {
+            self.successors.push(Vec::new());
+        }
+        id
+    }
+
+    /// Edge from 'u' to 'v', adds both nodes if needed.
+    pub fn add_edge(&mut self, u: T, v: T) {
+        let u = self.add_node(u);
+        let v = self.add_node(v);
+        self.successors[u].push(v);
+    }
+
+    pub fn id(&self, u: &T) -> Option<usize> {
+        self.nodes.get(u)
+    }
+
+    pub fn value(&self, id: usize) -> &T {
+        self.nodes.value(id)
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.successors.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.successors.is_empty()
+    }
+
+    pub fn successors(&self, u: usize) -> &[usize] {
+        &self.successors[u]
+    }
+
+    /// Every node comes before all of its successors, ties broken by the ids. Fails if there is a cycle.
+    pub fn topological_order(&self) -> std::result::Result<Vec<usize>, CycleError<T>> {
+        kahn(&self.successors, |id| id).map_err(|err| err.map(|id| self.value(id).clone()))
+    }
+
+    /// Path counting, see 'PathCounter'. Fails if the graph has a cycle, since then there can be infinitely many paths.
+    pub fn path_counter(&self) -> std::result::Result<PathCounter<'_, T>, CycleError<T>> {
+        let order = self.topological_order()?;
+        Ok(PathCounter { graph: self, order, to_target: HashMap::new() })
+    }
+}
+
+/// Counts paths in an acyclic 'Digraph'. The topological order is computed once,
+/// the number of paths from every node to a target is remembered for later queries of the same target.
+#[derive(Debug)]
+pub struct PathCounter<'a, T: Clone + Eq + Hash> {
+    graph: &'a Digraph<T>,
+    order: Vec<usize>,
+    to_target: HashMap<usize, Vec<u64>>,
+}
+
+impl<T: Clone + Eq + Hash> PathCounter<'_, T> {
+    /// Number of paths from 'from' to 'to'. A single node is a path to itself.
+    pub fn count(&mut self, from: usize, to: usize) -> u64 {
+        let (graph, order) = (self.graph, &self.order);
+        let counts = self.to_target.entry(to).or_insert_with(|| {
+            let mut counts = vec![0; graph.len()];
+            counts[to] = 1;
+            for &u in order.iter().rev() {
+                if u != to {
+                    counts[u] = graph.successors(u).iter().map(|&v| counts[v]).sum();
+                }
+            }
+            counts
+        });
+        counts[from]
+    }
+
+    /// Number of paths from 'from' to 'to' that visit all of 'must_visit', in any order.
+    /// Dynamic programming over subsets of 'must_visit', so keep it small (each node costs a factor of 2).
+    /// Fails if the subsets do not fit in a usize mask.
+    pub fn count_via(&self, from: usize, to: usize, must_visit: &[usize]) -> Result<u64> {
+        let graph = self.graph;
+        if must_visit.len() >= usize::BITS as usize {
+            return Err(format!("Cannot count paths via {} nodes, at most {} fit in a mask.", must_visit.len(), usize::BITS - 1).into());
+        }
+        let full = (1usize << must_visit.len()) - 1;
+        // Part of the mask each node stands for, zero for nodes that need not be visited.
+        let mut bits = vec![0usize; graph.len()];
+        for (idx, &u) in must_visit.iter().enumerate() {
+            bits[u] |= 1 << idx;
+        }
+
+        // counts[u][mask]: paths from 'u' to 'to' visiting exactly the 'mask' part of 'must_visit'.
+        let mut counts = vec![vec![0u64; full + 1]; graph.len()];
+        counts[to][bits[to]] = 1;
+        for &u in self.order.iter().rev() {
+            if u == to {
+                continue;
+            }
+            for &v in graph.successors(u) {
+                for mask in 0..=full {
+                    let paths = counts[v][mask];
+                    counts[u][mask | bits[u]] += paths;
+                }
+            }
+        }
+        Ok(counts[from][full])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digraph(edges: &[&str]) -> Digraph<char> {
+        let mut graph = Digraph::new();
+        for edge in edges {
+            let edge: Vec<char> = edge.chars().collect();
+            graph.add_edge(edge[0], edge[1]);
+        }
+        graph
+    }
+
+    #[test]
+    fn cycles() {
+        let graph = digraph(&["xa", "ab", "bc", "cd", "ca"]);
+        let err = graph.topological_order().unwrap_err();
+        assert_eq!(err.cycle, vec!['a', 'b', 'c']);
+        assert_eq!(err.to_string(), "cycle 'a' -> 'b' -> 'c' -> 'a'");
+        assert_eq!(graph.path_counter().unwrap_err(), err);
+
+        assert_eq!(digraph(&["xa", "ab", "bc", "cd"]).topological_order().unwrap(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn paths_via() {
+        // s -> t directly through a, or over a or b to c, then d or e to t.
+        let graph = digraph(&["sa", "sb", "ac", "bc", "cd", "ce", "dt", "et", "at"]);
+        let id = |u: char| graph.id(&u).unwrap();
+        let mut counter = graph.path_counter().unwrap();
+        assert_eq!(counter.count(id('s'), id('t')), 5);
+        assert_eq!(counter.count(id('c'), id('t')), 2);
+        assert_eq!(counter.count(id('t'), id('s')), 0);
+
+        let via = |nodes: &str| counter.count_via(id('s'), id('t'), &nodes.chars().map(id).collect::<Vec<_>>()).unwrap();
+        assert_eq!(via(""), 5);
+        assert_eq!(via("c"), 4);
+        assert_eq!(via("ad"), 1);
+        assert_eq!(via("de"), 0);
+        assert_eq!(via("st"), 5);
+        assert_eq!(via("cc"), 4);
+
+        assert!(counter.count_via(id('s'), id('t'), &[id('c'); 64]).is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::{Display, Write};
+use std::fs;
+use std::hash::Hash;
+use std::path::Path;
+use crate::circuit::{Circuit, Op};
+use crate::dag::Digraph;
+use crate::graph::Graph;
+use crate::Result;
+
+const HIGHLIGHT: &str = "color=\"blue\", penwidth=3";
+
+/// Graph in Graphviz DOT format, to be rendered offline, e.g. by 'dot -Tsvg dec24.dot -o dec24.svg'.
+/// Nodes and edges are written in the order they were added, node ids are always quoted.
+#[derive(Debug, Clone)]
+pub struct Dot {
+    name: String,
+    directed: bool,
+    nodes: Vec<(String, String)>,
+    edges: Vec<(String, String, String)>,
+    highlighted_nodes: HashSet<String>,
+    highlighted_edges: HashSet<(String, String)>,
+}
+
+/// "id" with quotes and backslashes escaped, line breaks turned to '\n'.
+fn quote(id: &str) -> String {
+    format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n"))
+}
+
+/// Attributes in brackets, with the highlighting ones added if needed.
+fn with_highlight(attrs: &str, highlighted: bool) -> String {
+    match (attrs.is_empty(), highlighted) {
+        (true, false) => String::new(),
+        (true, true) => format!(" [{}]", HIGHLIGHT),
+        (false, false) => format!(" [{}]", attrs),
+        (false, true) => format!(" [{}, {}]", attrs, HIGHLIGHT),
+    }
+}
+
+/// Attributes like 'color="red", shape="box"'.
+fn attributes(attrs: &[(&str, &str)]) -> String {
+    attrs.iter().map(|(key, value)| format!("{}={}", key, quote(value))).collect::<Vec<_>>().join(", ")
+}
+
+impl Dot {
+    /// Graph with directed edges, "a -> b".
+    pub fn digraph(name: &str) -> Self {
+        Self::new(name, true)
+    }
+
+    /// Graph with undirected edges, "a -- b".
+    pub fn graph(name: &str) -> Self {
+        Self::new(name, false)
+    }
+
+    fn new(name: &str, directed: bool) -> Self {
+        Self {
+            name: name.to_string(), directed, nodes: Vec::new(), edges: Vec::new(),
+            highlighted_nodes: HashSet::new(), highlighted_edges: HashSet::new(),
+        }
+    }
+
+    /// Node with attributes. Nodes used only by edges need not be added.
+    pub fn node(&mut self, id: impl Display, attrs: &[(&str, &str)]) -> &mut Self {
+        self.nodes.push((id.to_string(), attributes(attrs)));
+        self
+    }
+
+    pub fn edge(&mut self, from: impl Display, to: impl Display, attrs: &[(&str, &str)]) -> &mut Self {
+        self.edges.push((from.to_string(), to.to_string(), attributes(attrs)));
+        self
+    }
+
+    /// Draws given nodes in bold blue.
+    pub fn highlight_nodes<T: Display>(&mut self, ids: impl IntoIterator<Item = T>) -> &mut Self {
+        self.highlighted_nodes.extend(ids.into_iter().map(|id| id.to_string()));
+        self
+    }
+
+    /// Draws nodes of the path and edges between its consecutive nodes in bold blue.
+    pub fn highlight_path<T: Display>(&mut self, path: impl IntoIterator<Item = T>) -> &mut Self {
+        let path: Vec<String> = path.into_iter().map(|id| id.to_string()).collect();
+        for pair in path.windows(2) {
+            self.highlighted_edges.insert((pair[0].clone(), pair[1].clone()));
+            if !self.directed {
+                self.highlighted_edges.insert((pair[1].clone(), pair[0].clone()));
+            }
+        }
+        self.highlighted_nodes.extend(path);
+        self
+    }
+
+    /// Gates are nodes named by their output wires and colored by their operations, input wires are boxes.
+    pub fn from_circuit(circuit: &Circuit) -> Self {
+        let mut dot = Self::digraph("Circuit");
+        for (wire, value) in &circuit.states {
+            let label = format!("{}: {}", wire, u8::from(*value));
+            dot.node(wire, &[("shape", "box"), ("label", &label)]);
+        }
+        for (out, gate) in &circuit.gates {
+            let color = match gate.op {
+                Op::And => "red",
+                Op::Or => "green",
+                Op::Xor => "yellow",
+            };
+            let label = format!("{}\n{}", out, gate.op);
+            let shape = if out.starts_with('z') { "doublecircle" } else { "ellipse" };
+            dot.node(out, &[("label", &label), ("style", "filled"), ("fillcolor", color), ("shape", shape)]);
+            dot.edge(&gate.a, out, &[]);
+            dot.edge(&gate.b, out, &[]);
+        }
+        dot
+    }
+
+    pub fn from_digraph<T: Clone + Eq + Hash + Display>(graph: &Digraph<T>) -> Self {
+        let mut dot = Self::digraph("Digraph");
+        for u in 0..graph.len() {
+            dot.node(graph.value(u), &[]);
+            for &v in graph.successors(u) {
+                dot.edge(graph.value(u), graph.value(v), &[]);
+            }
+        }
+        dot
+    }
+
+    /// Every edge once, from the node added to the graph first.
+    pub fn from_graph<T: Clone + Eq + Hash + Display>(graph: &Graph<T>) -> Self {
+        let mut dot = Self::graph("Graph");
+        for u in 0..graph.len() {
+            dot.node(graph.value(u), &[]);
+            let mut neighbors: Vec<usize> = graph.neighbors(u).iter().copied().filter(|&v| v > u).collect();
+            neighbors.sort_unstable();
+            for v in neighbors {
+                dot.edge(graph.value(u), graph.value(v), &[]);
+            }
+        }
+        dot
+    }
+
+    /// Directed graph of a map from nodes to their successors, e.g. 'HashMap<String, Vec<String>>'.
+    /// Nodes are sorted, so that hash maps give the same output every time.
+    pub fn from_adjacency<K, I>(adjacency: impl IntoIterator<Item = (K, I)>) -> Self
+    where
+        K: Display,
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        let sorted: BTreeMap<String, Vec<String>> = adjacency.into_iter()
+            .map(|(u, next)| (u.to_string(), next.into_iter().map(|v| v.to_string()).collect()))
+            .collect();
+        let mut dot = Self::digraph("Adjacency");
+        for (u, next) in &sorted {
+            dot.node(u, &[]);
+            for v in next {
+                dot.edge(u, v, &[]);
+            }
+        }
+        dot
+    }
+
+    /// The whole DOT file.
+    pub fn render(&self) -> String {
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        let mut out = String::new();
+        // Writing to a String cannot fail.
+        writeln!(out, "{} {} {{", kind, quote(&self.name)).unwrap();
+        for (id, attrs) in &self.nodes {
+            let attrs = with_highlight(attrs, self.highlighted_nodes.contains(id));
+            writeln!(out, "  {}{};", quote(id), attrs).unwrap();
+        }
+        // Highlighted nodes that are only mentioned by edges. Ids the graph does not have at all are skipped.
+        let mut extra: Vec<&String> = self.highlighted_nodes.iter()
+            .filter(|id| !self.nodes.iter().any(|(n, _)| n == *id))
+            .filter(|id| self.edges.iter().any(|(from, to, _)| from == *id || to == *id))
+            .collect();
+        extra.sort();
+        for id in extra {
+            writeln!(out, "  {} [{}];", quote(id), HIGHLIGHT).unwrap();
+        }
+        for (from, to, attrs) in &self.edges {
+            let attrs = with_highlight(attrs, self.highlighted_edges.contains(&(from.clone(), to.clone())));
+            writeln!(out, "  {} {} {}{};", quote(from), arrow, quote(to), attrs).unwrap();
+        }
+        writeln!(out, "}}").unwrap();
+        out
+    }
+
+    /// Writes the DOT file, e.g. "dec24.dot".
+    pub fn write(&self, path: impl AsRef<Path>) -> Result<()> {
+        fs::write(path, self.render())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn circuit() {
+        let lines = ["x00: 1", "y00: 0", "", "x00 XOR y00 -> z00", "x00 AND y00 -> c"].map(String::from);
+        let circuit = Circuit::parse(&lines).unwrap();
+        let mut dot = Dot::from_circuit(&circuit);
+        dot.highlight_nodes(["z00", "q"]);
+        assert_eq!(dot.render(), concat!(
+            "digraph \"Circuit\" {\n",
+            "  \"x00\" [shape=\"box\", label=\"x00: 1\"];\n",
+            "  \"y00\" [shape=\"box\", label=\"y00: 0\"];\n",
+            "  \"c\" [label=\"c\\nAND\", style=\"filled\", fillcolor=\"red\", shape=\"ellipse\"];\n",
+            "  \"z00\" [label=\"z00\\nXOR\", style=\"filled\", fillcolor=\"yellow\", shape=\"doublecircle\", color=\"blue\", penwidth=3];\n",
+            "  \"x00\" -> \"c\";\n",
+            "  \"y00\" -> \"c\";\n",
+            "  \"x00\" -> \"z00\";\n",
+            "  \"y00\" -> \"z00\";\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn digraph() {
+        let mut graph = Digraph::new();
+        graph.add_edge("a", "b");
+        graph.add_edge("a", "c");
+        graph.add_edge("b", "c");
+        let mut dot = Dot::from_digraph(&graph);
+        dot.highlight_path(["a", "b", "c"]);
+        assert_eq!(dot.render(), concat!(
+            "digraph \"Digraph\" {\n",
+            "  \"a\" [color=\"blue\", penwidth=3];\n",
+            "  \"b\" [color=\"blue\", penwidth=3];\n",
+            "  \"c\" [color=\"blue\", penwidth=3];\n",
+            "  \"a\" -> \"b\" [color=\"blue\", penwidth=3];\n",
+            "  \"a\" -> \"c\";\n",
+            "  \"b\" -> \"c\" [color=\"blue\", penwidth=3];\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn graph() {
+        let graph = Graph::from_edges([("b", "a"), ("a", "c"), ("c", "b")]);
+        let mut dot = Dot::from_graph(&graph);
+        // Undirected, so the path may go against the order the edges are written in.
+        dot.highlight_path(["c", "a"]);
+        assert_eq!(dot.render(), concat!(
+            "graph \"Graph\" {\n",
+            "  \"b\";\n",
+            "  \"a\" [color=\"blue\", penwidth=3];\n",
+            "  \"c\" [color=\"blue\", penwidth=3];\n",
+            "  \"b\" -- \"a\";\n",
+            "  \"b\" -- \"c\";\n",
+            "  \"a\" -- \"c\" [color=\"blue\", penwidth=3];\n",
+            "}\n",
+        ));
+    }
+
+    #[test]
+    fn adjacency() {
+        let adjacency = HashMap::from([("b", vec!["c"]), ("a", vec!["b", "c"])]);
+        let mut dot = Dot::from_adjacency(adjacency);
+        dot.highlight_nodes(["c", "nowhere"]).edge("c", "d", &[("style", "dashed")]);
+        dot.highlight_nodes(["d"]);
+        assert_eq!(dot.render(), concat!(
+            "digraph \"Adjacency\" {\n",
+            "  \"a\";\n",
+            "  \"b\";\n",
+            "  \"c\" [color=\"blue\", penwidth=3];\n",
+            "  \"d\" [color=\"blue\", penwidth=3];\n",
+            "  \"a\" -> \"b\";\n",
+            "  \"a\" -> \"c\";\n",
+            "  \"b\" -> \"c\";\n",
+            "  \"c\" -> \"d\" [style=\"dashed\"];\n",
+            "}\n",
+        ));
+    }
+}
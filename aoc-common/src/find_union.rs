@@ -0,0 +1,248 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+use crate::interner::Interner;
+
+/// Find-Union or Disjoint-Set data structure over dense indices 0 .. len.
+/// Keeps the size of every set and the number of sets, so both are available in O(1).
+#[derive(Debug, Clone, Default)]
+pub struct DisjointSets {
+    parents: Vec<usize>,    // Roots point to themselves.
+    sizes: Vec<usize>,      // Only valid for roots.
+    count: usize,
+}
+
+impl DisjointSets {
+    /// Creates 'len' single-element sets.
+    pub fn new(len: usize) -> Self {
+        Self { parents: (0..len).collect(), sizes: vec![1; len], count: len }
+    }
+
+    /// Adds a new single-element set, returns its index.
+    pub fn add(&mut self) -> usize {
+        let idx = self.parents.len();
+        self.parents.push(idx);
+        self.sizes.push(1);
+        self.count += 1;
+        idx
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parents.is_empty()
+    }
+
+    /// Number of sets.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Representant of the set containing u. Panics if u is out of range.
+    pub fn find(&mut self, u: usize) -> usize {
+        let mut root = u;
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut idx = u;
+        while self.parents[idx] != root {
+            let next = self.parents[idx];
+            self.parents[idx] = root;
+            idx = next;
+        }
+        root
+    }
+
+    /// Joins sets of u and v, the smaller one goes under the larger one.
+    /// Returns true if two sets were joined. False is they were already in the same set.
+    pub fn join(&mut self, u: usize, v: usize) -> bool {
+        let mut ru = self.find(u);
+        let mut rv = self.find(v);
+        if ru == rv { return false; }
+        if self.sizes[ru] < self.sizes[rv] {
+            (ru, rv) = (rv, ru);
+        }
+        self.parents[rv] = ru;
+        self.sizes[ru] += self.sizes[rv];
+        self.count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+
+    /// Number of elements in the set containing u.
+    pub fn size(&mut self, u: usize) -> usize {
+        let root = self.find(u);
+        self.sizes[root]
+    }
+
+    /// Sizes of all the sets, largest first.
+    pub fn set_sizes(&self) -> Vec<usize> {
+        let mut sizes = (0..self.len()).filter(|&idx| self.parents[idx] == idx).map(|idx| self.sizes[idx]).collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        sizes
+    }
+
+    /// Returns all the sets, elements of each in increasing order.
+    pub fn sets(&mut self) -> Vec<Vec<usize>> {
+        let mut roots = HashMap::<usize, Vec<usize>>::new();
+        for idx in 0..self.len() {
+            let root = self.find(idx);
+            roots.entry(root).or_default().push(idx);
+        }
+        roots.into_values().collect()
+    }
+}
+
+/// Find-Union over arbitrary values. Interns them and keeps the sets in 'DisjointSets'.
+#[derive(Debug, Clone)]
+pub struct FindUnion<T: Clone + PartialEq + Eq + Hash> {
+    // @note It is not possible to add two nodes with the same value.
+    nodes: Interner<T>,
+    sets: DisjointSets,
+}
+
+impl<T: Clone + PartialEq + Eq + Hash> Default for FindUnion<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + PartialEq + Eq + Hash> FindUnion<T> {
+    /// Creates empty Find-Union structure.
+    pub fn new() -> Self
+    {
+        Self { nodes: Interner::new(), sets: DisjointSets::default() }
+    }
+
+    fn node(&mut self, u: T) -> usize {
+        let idx = self.nodes.intern(u);
+        if idx == self.sets.len() {
+            self.sets.add();
+        }
+        idx
+    }
+
+    /// Returns true if u is in any set.
+    /// (If u is not in any set it will NOT be added.)
+    pub fn contains(&self, u: T) -> bool {
+        self.nodes.get(&u).is_some()
+    }
+
+    /// Make sure that u is in some set. If it is not, a new set is created for it.
+    /// Returns true if u was added. False it was already in some set.
+    pub fn ensure(&mut self, u: T) -> bool {
+        if self.contains(u.clone()) {
+            return false;
+        }
+        self.node(u);
+        true
+    }
+
+    /// Joins sets of u and v.
+    /// Returns true if two sets were joined. False is they were already in the same set.
+    /// (If u or v are not in any set they will be added.)
+    pub fn join(&mut self, u: T, v: T) -> bool {
+        let nu = self.node(u);
+        let nv = self.node(v);
+        self.sets.join(nu, nv)
+    }
+
+    /// Finds representant of set containing u.
+    /// (If u is not in any set it will be added.)
+    pub fn find_root(&mut self, u: T) -> T {
+        let idx = self.node(u);
+        let root_idx = self.sets.find(idx);
+        self.nodes.value(root_idx).clone()
+    }
+
+    /// Return true if u and v are in the same set.
+    /// (If u or v are not in any set they will be added.)
+    pub fn connected(&mut self, u: T, v: T) -> bool {
+        let nu = self.node(u);
+        let nv = self.node(v);
+        self.sets.connected(nu, nv)
+    }
+
+    /// Number of elements in the set containing u.
+    /// (If u is not in any set it will be added.)
+    pub fn size(&mut self, u: T) -> usize {
+        let idx = self.node(u);
+        self.sets.size(idx)
+    }
+
+    /// Number of sets.
+    pub fn count(&self) -> usize {
+        self.sets.count()
+    }
+
+    /// Sizes of all the sets, largest first.
+    pub fn set_sizes(&self) -> Vec<usize> {
+        self.sets.set_sizes()
+    }
+
+    /// Returns all the sets.
+    pub fn get_sets(&mut self) -> Vec<HashSet<T>> {
+        let sets = self.sets.sets();
+        sets.into_iter().map(|set| set.into_iter().map(|idx| self.nodes.value(idx).clone()).collect()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_by_size() {
+        let mut sets = DisjointSets::new(5);
+        assert!(sets.join(0, 1));
+        assert!(sets.join(3, 0));
+        // The single 3 goes under the pair, not the other way round.
+        assert_eq!(sets.parents[3], 0);
+        assert_eq!(sets.find(3), 0);
+        assert!(!sets.join(1, 3));
+        assert_eq!(sets.size(1), 3);
+        assert_eq!(sets.size(4), 1);
+        assert!(sets.connected(1, 3));
+        assert!(!sets.connected(2, 4));
+    }
+
+    #[test]
+    fn counts() {
+        let mut sets = DisjointSets::new(4);
+        assert_eq!(sets.count(), 4);
+        sets.join(0, 1);
+        sets.join(2, 3);
+        sets.join(1, 0);
+        assert_eq!(sets.count(), 2);
+        assert_eq!(sets.add(), 4);
+        assert_eq!((sets.len(), sets.count()), (5, 3));
+        sets.join(4, 2);
+        assert_eq!(sets.set_sizes(), vec![3, 2]);
+        let mut all = sets.sets();
+        all.sort();
+        assert_eq!(all, vec![vec![0, 1], vec![2, 3, 4]]);
+    }
+
+    #[test]
+    fn values() {
+        let mut find_union = FindUnion::new();
+        assert!(find_union.ensure("a"));
+        assert!(!find_union.ensure("a"));
+        assert!(find_union.join("b", "c"));
+        assert!(find_union.join("a", "c"));
+        assert!(find_union.ensure("d"));
+        assert!(!find_union.contains("e"));
+        assert_eq!(find_union.count(), 2);
+        assert_eq!(find_union.size("b"), 3);
+        assert_eq!(find_union.find_root("a"), find_union.find_root("c"));
+        let mut sets = find_union.get_sets();
+        sets.sort_by_key(|set| set.len());
+        assert_eq!(sets, vec![HashSet::from(["d"]), HashSet::from(["a", "b", "c"])]);
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::{BTreeSet, HashSet};
+use std::hash::Hash;
+use crate::find_union::DisjointSets;
+use crate::interner::Interner;
+
+/// Undirected graph without multi-edges. Nodes are values of T, interned to dense ids in order of appearance.
+/// All the queries work with the ids, 'value' turns them back to T.
+#[derive(Debug, Clone)]
+pub struct Graph<T: Clone + Eq + Hash> {
+    nodes: Interner<T>,
+    adjacent: Vec<HashSet<usize>>,
+}
+
+impl<T: Clone + Eq + Hash> Default for Graph<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Eq + Hash> Graph<T> {
+    pub fn new() -> Self {
+        Self { nodes: Interner::new(), adjacent: Vec::new() }
+    }
+
+    pub fn from_edges(edges: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut graph = Self::new();
+        for (u, v) in edges {
+            graph.add_edge(u, v);
+        }
+        graph
+    }
+
+    /// Id of the node, a new one if it is not in the graph yet.
+    pub fn add_node(&mut self, u: T) -> usize {
+        let id = self.nodes.intern(u);
+        if id == self.adjacent.len() {
+            self.adjacent.push(HashSet::new());
+        }
+        id
+    }
+
+    /// Adds both nodes if needed. Returns false if the edge was already there.
+    /// A self-loop only adds the node (and returns false), so that cliques and triangles stay simple.
+    pub fn add_edge(&mut self, u: T, v: T) -> bool {
+        let u = self.add_node(u);
+        let v = self.add_node(v);
+        if u == v {
+            return false;
+        }
+        self.adjacent[v].insert(u);
+        self.adjacent[u].insert(v)
+    }
+
+    pub fn id(&self, u: &T) -> Option<usize> {
+        self.nodes.get(u)
+    }
+
+    pub fn value(&self, id: usize) -> &T {
+        self.nodes.value(id)
+    }
+
+    /// Values of given nodes.
+    pub fn values<'a>(&'a self, ids: &'a [usize]) -> impl Iterator<Item = &'a T> + 'a {
+        ids.iter().map(|&id| self.value(id))
+    }
+
+    /// Number of nodes.
+    pub fn len(&self) -> usize {
+        self.adjacent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.adjacent.is_empty()
+    }
+
+    pub fn neighbors(&self, u: usize) -> &HashSet<usize> {
+        &self.adjacent[u]
+    }
+
+    pub fn degree(&self, u: usize) -> usize {
+        self.adjacent[u].len()
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.adjacent[u].contains(&v)
+    }
+
+    /// All the triangles, each once with its ids in increasing order.
+    pub fn triangles(&self) -> Vec<[usize; 3]> {
+        let mut result = Vec::new();
+        for u in 0..self.len() {
+            for &v in self.adjacent[u].iter().filter(|&&v| v > u) {
+                for &w in self.adjacent[v].iter().filter(|&&w| w > v) {
+                    if self.has_edge(u, w) {
+                        result.push([u, v, w]);
+                    }
+                }
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    /// Connected components, ids of each in increasing order, components ordered by their smallest ids.
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        let mut sets = DisjointSets::new(self.len());
+        for u in 0..self.len() {
+            for &v in &self.adjacent[u] {
+                sets.join(u, v);
+            }
+        }
+        let mut components = sets.sets();
+        components.sort_unstable();
+        components
+    }
+
+    /// Nodes in degeneracy order (repeatedly removing a node of the smallest degree)
+    /// together with the core number of every node: the largest k such that the node is in the k-core.
+    pub fn degeneracy(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut degrees: Vec<usize> = (0..self.len()).map(|u| self.degree(u)).collect();
+        let mut queue: BTreeSet<(usize, usize)> = (0..self.len()).map(|u| (degrees[u], u)).collect();
+        let mut removed = vec![false; self.len()];
+        let mut cores = vec![0; self.len()];
+        let mut order = Vec::with_capacity(self.len());
+        let mut k = 0;
+        while let Some((degree, u)) = queue.pop_first() {
+            k = k.max(degree);
+            cores[u] = k;
+            removed[u] = true;
+            order.push(u);
+            for &v in &self.adjacent[u] {
+                if !removed[v] {
+                    queue.remove(&(degrees[v], v));
+                    degrees[v] -= 1;
+                    queue.insert((degrees[v], v));
+                }
+            }
+        }
+        (order, cores)
+    }
+
+    /// Core numbers of all the nodes, see 'degeneracy'.
+    pub fn core_numbers(&self) -> Vec<usize> {
+        self.degeneracy().1
+    }
+
+    /// Nodes of the k-core: the largest subgraph where every node has at least k neighbours.
+    pub fn k_core(&self, k: usize) -> Vec<usize> {
+        let cores = self.core_numbers();
+        (0..self.len()).filter(|&u| cores[u] >= k).collect()
+    }
+
+    /// All the maximal cliques, ids of each in increasing order.
+    /// Bron–Kerbosch with pivoting, the top level goes in degeneracy order to keep the candidate sets small.
+    pub fn maximal_cliques(&self) -> Vec<Vec<usize>> {
+        let (order, _) = self.degeneracy();
+        let mut position = vec![0; self.len()];
+        for (idx, &u) in order.iter().enumerate() {
+            position[u] = idx;
+        }
+
+        let mut result = Vec::new();
+        for &u in &order {
+            let (later, earlier) = self.adjacent[u].iter().partition(|&&v| position[v] > position[u]);
+            self.bron_kerbosch(&mut vec![u], later, earlier, &mut result);
+        }
+        result
+    }
+
+    /// 'clique' can be extended by 'candidates', but not by 'rejected' (those cliques were reported already).
+    fn bron_kerbosch(&self, clique: &mut Vec<usize>, mut candidates: HashSet<usize>, mut rejected: HashSet<usize>, result: &mut Vec<Vec<usize>>) {
+        if candidates.is_empty() {
+            if rejected.is_empty() {
+                let mut found = clique.clone();
+                found.sort_unstable();
+                result.push(found);
+            }
+            return;
+        }
+
+        // Every maximal clique contains the pivot or one of its non-neighbours, so only those need to be tried.
+        let pivot = *candidates.iter().chain(rejected.iter())
+            .max_by_key(|&&p| candidates.iter().filter(|&v| self.has_edge(p, *v)).count())
+            .unwrap();
+        let tries: Vec<usize> = candidates.iter().copied().filter(|&v| !self.has_edge(pivot, v)).collect();
+        for v in tries {
+            let neighbors = &self.adjacent[v];
+            clique.push(v);
+            self.bron_kerbosch(clique, candidates.intersection(neighbors).copied().collect(), rejected.intersection(neighbors).copied().collect(), result);
+            clique.pop();
+            candidates.remove(&v);
+            rejected.insert(v);
+        }
+    }
+
+    /// One of the largest cliques, ids in increasing order.
+    pub fn maximum_clique(&self) -> Vec<usize> {
+        self.maximal_cliques().into_iter().max_by_key(|clique| clique.len()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Clique abcd, triangle def, edge fg and lonely h. Ids are in alphabetical order.
+    fn graph() -> Graph<char> {
+        let edges = ["ab", "ac", "ad", "bc", "bd", "cd", "de", "df", "ef", "fg"];
+        let mut graph = Graph::from_edges(edges.map(|edge| (edge.as_bytes()[0] as char, edge.as_bytes()[1] as char)));
+        graph.add_node('h');
+        graph
+    }
+
+    #[test]
+    fn self_loops() {
+        let mut graph = graph();
+        assert!(!graph.add_edge('a', 'a'));
+        assert!(!graph.add_edge('i', 'i'));
+        assert_eq!(graph.id(&'i'), Some(8));
+        assert!(!graph.has_edge(0, 0));
+        assert_eq!(graph.degree(0), 3);
+        assert_eq!(graph.triangles().len(), 5);
+    }
+
+    #[test]
+    fn cores() {
+        let graph = graph();
+        assert_eq!(graph.core_numbers(), vec![3, 3, 3, 3, 2, 2, 1, 0]);
+        assert_eq!(graph.k_core(3), vec![0, 1, 2, 3]);
+        assert_eq!(graph.k_core(2), vec![0, 1, 2, 3, 4, 5]);
+        assert_eq!(graph.k_core(4), Vec::<usize>::new());
+        assert_eq!(graph.components(), vec![vec![0, 1, 2, 3, 4, 5, 6], vec![7]]);
+    }
+
+    #[test]
+    fn cliques() {
+        let graph = graph();
+        assert_eq!(graph.triangles(), vec![[0, 1, 2], [0, 1, 3], [0, 2, 3], [1, 2, 3], [3, 4, 5]]);
+        let mut cliques = graph.maximal_cliques();
+        cliques.sort();
+        assert_eq!(cliques, vec![vec![0, 1, 2, 3], vec![3, 4, 5], vec![5, 6], vec![7]]);
+        let clique: String = graph.values(&graph.maximum_clique()).collect();
+        assert_eq!(clique, "abcd");
+    }
+}
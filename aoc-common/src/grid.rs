@@ -0,0 +1,272 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+use crate::parse_error::{ParseError, ParseResult};
+use crate::vec2::Vec2;
+
+/// Offsets of the 4 orthogonal neighbours, clockwise from up.
+pub const ORTHOGONAL: [Vec2; 4] = [Vec2::new(0, -1), Vec2::new(1, 0), Vec2::new(0, 1), Vec2::new(-1, 0)];
+/// Offsets of the 4 diagonal neighbours, clockwise from up-right.
+pub const DIAGONAL: [Vec2; 4] = [Vec2::new(1, -1), Vec2::new(1, 1), Vec2::new(-1, 1), Vec2::new(-1, -1)];
+/// Offsets of all the 8 neighbours (Moore neighbourhood), clockwise from up.
+pub const MOORE: [Vec2; 8] = [
+    Vec2::new(0, -1), Vec2::new(1, -1), Vec2::new(1, 0), Vec2::new(1, 1),
+    Vec2::new(0, 1), Vec2::new(-1, 1), Vec2::new(-1, 0), Vec2::new(-1, -1),
+];
+
+/// Offsets at Manhattan distance 1 to 'radius', row by row.
+pub fn diamond(radius: usize) -> Vec<Vec2> {
+    let radius = radius as i32;
+    let mut result = Vec::new();
+    for dy in -radius..=radius {
+        let width = radius - dy.abs();
+        for dx in -width..=width {
+            if dx != 0 || dy != 0 {
+                result.push(Vec2::new(dx, dy));
+            }
+        }
+    }
+    result
+}
+
+/// Rectangular grid of cells, stored row by row in a single Vec. (0,0) is top-left.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// 'cells' go row by row, there must be exactly width * height of them.
+    pub fn from_vec(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(cells.len(), width * height, "{} cells do not make a {} x {} grid", cells.len(), width, height);
+        Grid { width, height, cells }
+    }
+
+    /// Builds the grid from text lines, mapping each character by 'cell'.
+    /// Characters 'cell' rejects are reported as errors, 'expected' says what should have been there instead.
+    /// All the lines must have the same length as the first one.
+    pub fn from_lines(lines: &[String], mut cell: impl FnMut(char) -> Option<T>, expected: &str) -> ParseResult<Self> {
+        let width = lines.first().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (idx, line) in lines.iter().enumerate() {
+            let mut length = 0;
+            for (col, c) in line.chars().enumerate() {
+                if col == width {
+                    return Err(ParseError::new(idx, line, col, format!("end of line after {} characters, as on the first line", width)));
+                }
+                cells.push(cell(c).ok_or_else(|| ParseError::new(idx, line, col, expected))?);
+                length += 1;
+            }
+            if length < width {
+                return Err(ParseError::new(idx, line, length, format!("{} characters, as on the first line", width)));
+            }
+        }
+
+        Ok(Grid { width, height: lines.len(), cells })
+    }
+
+    pub fn contains(&self, pos: Vec2) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn index_of(&self, pos: Vec2) -> Option<usize> {
+        if !self.contains(pos) {
+            return None;
+        }
+        Some(pos.y as usize * self.width + pos.x as usize)
+    }
+
+    fn pos_of(&self, index: usize) -> Vec2 {
+        Vec2::new((index % self.width) as i32, (index / self.width) as i32)
+    }
+
+    pub fn get(&self, pos: Vec2) -> Option<&T> {
+        self.index_of(pos).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, pos: Vec2) -> Option<&mut T> {
+        self.index_of(pos).map(|i| &mut self.cells[i])
+    }
+
+    /// Panics if the row is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks() does not accept 0, but then there are no cells anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// All the cells, row by row.
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    /// All the positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2> + '_ {
+        (0..self.cells.len()).map(|i| self.pos_of(i))
+    }
+
+    /// Cells together with their positions, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2, &T)> {
+        self.cells.iter().enumerate().map(|(i, cell)| (self.pos_of(i), cell))
+    }
+
+    /// Positions 'pos + dir' for all 'dirs' that are inside the grid.
+    pub fn neighbors<'a>(&'a self, pos: Vec2, dirs: &'a [Vec2]) -> impl Iterator<Item = Vec2> + 'a {
+        dirs.iter().map(move |&dir| pos + dir).filter(|&next| self.contains(next))
+    }
+
+    /// In-bounds orthogonal neighbours.
+    pub fn neighbors4(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.neighbors(pos, &ORTHOGONAL)
+    }
+
+    /// In-bounds diagonal neighbours.
+    pub fn diagonal_neighbors(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.neighbors(pos, &DIAGONAL)
+    }
+
+    /// In-bounds orthogonal and diagonal neighbours.
+    pub fn neighbors8(&self, pos: Vec2) -> impl Iterator<Item = Vec2> + '_ {
+        self.neighbors(pos, &MOORE)
+    }
+
+    /// Values at 'pos + dir' for all 'dirs', 'default' where it is out of bounds.
+    pub fn neighbor_values<'a>(&'a self, pos: Vec2, dirs: &'a [Vec2], default: &'a T) -> impl Iterator<Item = &'a T> + 'a {
+        dirs.iter().map(move |&dir| self.get(pos + dir).unwrap_or(default))
+    }
+
+    /// Number of in-bounds neighbours in 'dirs' whose values satisfy 'pred'.
+    pub fn count_neighbors(&self, pos: Vec2, dirs: &[Vec2], pred: impl Fn(&T) -> bool) -> usize {
+        dirs.iter().filter_map(|&dir| self.get(pos + dir)).filter(|&value| pred(value)).count()
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn count(&self, value: &T) -> usize {
+        self.cells.iter().filter(|&cell| cell == value).count()
+    }
+
+    /// The first position holding 'value', row by row.
+    pub fn find(&self, value: &T) -> Option<Vec2> {
+        self.cells.iter().position(|cell| cell == value).map(|i| self.pos_of(i))
+    }
+}
+
+impl<T> Index<Vec2> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Vec2) -> &T {
+        match self.index_of(pos) {
+            Some(i) => &self.cells[i],
+            None => panic!("{:?} is out of bounds of {} x {} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T> IndexMut<Vec2> for Grid<T> {
+    fn index_mut(&mut self, pos: Vec2) -> &mut T {
+        match self.index_of(pos) {
+            Some(i) => &mut self.cells[i],
+            None => panic!("{:?} is out of bounds of {} x {} grid", pos, self.width, self.height),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Grid ({} x {}):", self.width, self.height)?;
+        for row in self.rows() {
+            write!(f, " ")?;
+            for cell in row {
+                write!(f, " {}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(text: &[&str]) -> Vec<String> {
+        text.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn digits(text: &[&str]) -> ParseResult<Grid<u32>> {
+        Grid::from_lines(&lines(text), |c| c.to_digit(10), "digit")
+    }
+
+    #[test]
+    fn from_lines() {
+        let grid = digits(&["123", "456"]).unwrap();
+        assert_eq!((grid.width, grid.height), (3, 2));
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid[Vec2::new(2, 0)], 3);
+        assert_eq!(digits(&[]).unwrap(), Grid::from_vec(0, 0, vec![]));
+    }
+
+    #[test]
+    fn ragged_lines() {
+        let err = digits(&["123", "4567"]).unwrap_err();
+        assert_eq!((err.line_idx, err.column, err.text.as_str()), (1, 3, "4567"));
+        assert_eq!(err.expected, "end of line after 3 characters, as on the first line");
+
+        let err = digits(&["123", "456", "78"]).unwrap_err();
+        assert_eq!((err.line_idx, err.column, err.text.as_str()), (2, 2, "78"));
+        assert_eq!(err.expected, "3 characters, as on the first line");
+    }
+
+    #[test]
+    fn rejected_character() {
+        let err = digits(&["123", "4x6"]).unwrap_err();
+        assert_eq!((err.line_idx, err.column, err.expected.as_str()), (1, 1, "digit"));
+        assert_eq!(err.file, None);
+
+        let err = err.in_file("dec1.ex.txt");
+        assert_eq!(err.file.as_deref(), Some("dec1.ex.txt"));
+        assert_eq!(err.to_string(), "dec1.ex.txt:2:2: expected digit\n  4x6\n   ^");
+    }
+
+    fn positions(coords: &[(i32, i32)]) -> Vec<Vec2> {
+        coords.iter().map(|&(x, y)| Vec2::new(x, y)).collect()
+    }
+
+    #[test]
+    fn border_neighbors() {
+        // 1 2 3
+        // 4 5 6
+        let grid = digits(&["123", "456"]).unwrap();
+        assert_eq!(grid.neighbors4(Vec2::new(0, 0)).collect::<Vec<_>>(), positions(&[(1, 0), (0, 1)]));
+        assert_eq!(grid.neighbors4(Vec2::new(2, 1)).collect::<Vec<_>>(), positions(&[(2, 0), (1, 1)]));
+        assert_eq!(grid.neighbors4(Vec2::new(1, 0)).collect::<Vec<_>>(), positions(&[(2, 0), (1, 1), (0, 0)]));
+        assert_eq!(grid.neighbors8(Vec2::new(0, 1)).collect::<Vec<_>>(), positions(&[(0, 0), (1, 0), (1, 1)]));
+        assert_eq!(grid.diagonal_neighbors(Vec2::new(2, 0)).collect::<Vec<_>>(), positions(&[(1, 1)]));
+        assert_eq!(Grid::new(1, 1, 0).neighbors8(Vec2::new(0, 0)).count(), 0);
+    }
+
+    #[test]
+    fn border_values() {
+        let grid = digits(&["123", "456"]).unwrap();
+        assert_eq!(grid.neighbor_values(Vec2::new(0, 0), &ORTHOGONAL, &0).collect::<Vec<_>>(), vec![&0, &2, &4, &0]);
+        assert_eq!(grid.neighbor_values(Vec2::new(1, 1), &MOORE, &0).collect::<Vec<_>>(), vec![&2, &3, &6, &0, &0, &0, &4, &1]);
+        assert_eq!(grid.count_neighbors(Vec2::new(2, 1), &MOORE, |_| true), 3);
+        assert_eq!(grid.count_neighbors(Vec2::new(1, 0), &MOORE, |&value| value > 3), 3);
+        assert_eq!(grid.count_neighbors(Vec2::new(0, 0), &diamond(2), |_| true), 4);
+    }
+}
@@ -0,0 +1,42 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use crate::Result;
+
+/// Environment variable with directory of the input files. When set, no other directory is searched.
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+/// Name of the input file of given day.
+/// Variant 'in' is the puzzle input, 'ex' is the example, 'ex2' is the second example, etc.
+pub fn input_filename(day: u32, variant: &str) -> String {
+    format!("dec{}.{}.txt", day, variant)
+}
+
+/// Directories that are searched for the input files, in order.
+/// 'crate_dir' is the directory where the year's crate keeps its inputs (next to its sources).
+pub fn input_dirs(crate_dir: &Path) -> Vec<PathBuf> {
+    if let Some(dir) = env::var_os(INPUT_DIR_VAR) {
+        return vec![PathBuf::from(dir)];
+    }
+
+    let mut dirs = Vec::new();
+    if let Ok(cwd) = env::current_dir() {
+        dirs.push(cwd);
+    }
+    let crate_dir = crate_dir.to_path_buf();
+    if !dirs.contains(&crate_dir) {
+        dirs.push(crate_dir);
+    }
+    dirs
+}
+
+/// Finds the input file of given day. Error lists all the paths that were tried.
+pub fn locate_input(crate_dir: &Path, day: u32, variant: &str) -> Result<PathBuf> {
+    let filename = input_filename(day, variant);
+    let candidates: Vec<PathBuf> = input_dirs(crate_dir).iter().map(|dir| dir.join(&filename)).collect();
+    if let Some(path) = candidates.iter().find(|path| path.is_file()) {
+        return Ok(path.clone());
+    }
+
+    let searched = candidates.iter().map(|path| format!("  {}", path.display())).collect::<Vec<_>>().join("\n");
+    Err(format!("Input file {} not found (set {} to override). Searched:\n{}", filename, INPUT_DIR_VAR, searched).into())
+}
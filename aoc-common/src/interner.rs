@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Gives values dense ids 0, 1, 2, ... in order of their first appearance, so that they can index Vecs.
+#[derive(Debug, Clone)]
+pub struct Interner<T: Clone + Eq + Hash> {
+    ids: HashMap<T, usize>,
+    values: Vec<T>,
+}
+
+impl<T: Clone + Eq + Hash> Default for Interner<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Clone + Eq + Hash> Interner<T> {
+    pub fn new() -> Self {
+        Self { ids: HashMap::new(), values: Vec::new() }
+    }
+
+    /// Id of 'value', a new one if it has not been seen yet.
+    pub fn intern(&mut self, value: T) -> usize {
+        if let Some(&id) = self.ids.get(&value) {
+            return id;
+        }
+        let id = self.values.len();
+        self.ids.insert(value.clone(), id);
+        self.values.push(value);
+        id
+    }
+
+    /// Id of 'value' if it has been seen already.
+    pub fn get(&self, value: &T) -> Option<usize> {
+        self.ids.get(value).copied()
+    }
+
+    /// Value of the id. Panics if there is no such id.
+    pub fn value(&self, id: usize) -> &T {
+        &self.values[id]
+    }
+
+    /// All the values, indexed by their ids.
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ids() {
+        let mut interner = Interner::new();
+        assert!(interner.is_empty());
+        assert_eq!(interner.intern("x"), 0);
+        assert_eq!(interner.intern("y"), 1);
+        assert_eq!(interner.intern("x"), 0);
+        assert_eq!(interner.get(&"y"), Some(1));
+        assert_eq!(interner.get(&"z"), None);
+        assert_eq!(interner.value(1), &"y");
+        assert_eq!(interner.values(), &["x", "y"]);
+        assert_eq!(interner.len(), 2);
+    }
+}
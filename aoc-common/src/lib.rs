@@ -0,0 +1,35 @@
+//! Code shared by the solutions of all the years.
+
+#[macro_use]
+extern crate maplit;
+
+pub mod basic_parsing;
+pub mod parse_error;
+pub mod vec2;
+pub mod grid;
+pub mod matrix;
+pub mod maze;
+pub mod search;
+pub mod interner;
+pub mod find_union;
+pub mod spanning_tree;
+pub mod graph;
+pub mod topo_sort;
+pub mod dag;
+pub mod circuit;
+pub mod dot;
+pub mod cycle;
+pub mod obstacles;
+pub mod regions;
+pub mod cheats;
+pub mod inputs;
+pub mod answers;
+pub mod runner;
+pub mod solver;
+pub mod timing;
+#[cfg(feature = "bench")]
+pub mod bench;
+
+use std::error::Error;
+
+pub type Result<T> = std::result::Result<T, Box<dyn Error>>;
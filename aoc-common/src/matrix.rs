@@ -0,0 +1,267 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::ops::Deref;
+use itertools::Itertools;
+use crate::basic_parsing::read_lines;
+use crate::grid::Grid;
+use crate::parse_error::{ParseError, ParseResult};
+use crate::vec2::Vec2;
+
+/// Grid of ASCII characters, the usual shape of the puzzle maps.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Matrix {
+    pub grid: Grid<u8>,
+}
+
+/// Gives access to width, height and the Grid methods not redefined below for characters.
+impl Deref for Matrix {
+    type Target = Grid<u8>;
+
+    fn deref(&self) -> &Grid<u8> {
+        &self.grid
+    }
+}
+
+// To use the `{}` marker, the trait `fmt::Display` must be implemented
+// manually for the type.
+impl fmt::Display for Matrix {
+    // This trait requires `fmt` with this exact signature.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Matrix ({} x {}):", self.width, self.height)?;
+        for row in self.lines() {
+            writeln!(f, "  {}", row)?;
+        }
+        Ok(())
+    }
+}
+
+impl Matrix {
+    pub fn new(width: usize, height: usize, fill: char, walls: Option<char>) -> Self {
+        let mut matrix = Matrix { grid: Grid::new(width, height, ascii(fill)) };
+
+        if let Some(wall) = walls {
+            for y in 0..height {
+                for x in 0..width {
+                    if (x == 0) || (x == width - 1) || (y == 0) || (y == height - 1) {
+                        matrix.put(Vec2::new(x as i32, y as i32), wall);
+                    }
+                }
+            }
+        }
+
+        matrix
+    }
+
+    pub fn get(&self, pos: Vec2) -> Option<char> {
+        self.grid.get(pos).map(|&b| b as char)
+    }
+
+    pub fn get_row(&self, row_idx: i32) -> Option<&str> {
+        if !self.contains_row(row_idx) {
+            return None;
+        }
+        Some(as_str(self.grid.row(row_idx as usize)))
+    }
+
+    /// All the rows, top to bottom.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.grid.rows().map(as_str)
+    }
+
+    pub fn get_int(&self, pos: Vec2) -> Option<i32> {
+        if let Some(c) = self.get(pos) {
+            return Some(c.to_digit(10).unwrap() as i32);
+        }
+        None
+    }
+
+    pub fn get_int_row(&self, row_idx: i32) -> Option<Vec<i32>> {
+        if let Some(line) = self.get_row(row_idx) {
+            return Some(line.chars().map(|c| c.to_digit(10).unwrap() as i32).collect_vec());
+        }
+        None
+    }
+
+    pub fn put(&mut self, pos: Vec2, c: char) {
+        match self.grid.get_mut(pos) {
+            Some(cell) => *cell = ascii(c),
+            None => panic!("{:?} is out of bounds of {}", pos, self),
+        }
+    }
+
+    pub fn contains_row(&self, row_idx: i32) -> bool {
+        if row_idx < 0 { return false; }
+        if row_idx >= (self.height as i32) { return false; }
+        true
+    }
+
+    pub fn count(&self, c: char) -> i32 {
+        if !c.is_ascii() {
+            return 0;
+        }
+        self.grid.count(&(c as u8)) as i32
+    }
+
+    /// Characters at 'pos + dir' for all 'dirs', 'default' where it is out of bounds.
+    pub fn neighbor_chars<'a>(&'a self, pos: Vec2, dirs: &'a [Vec2], default: char) -> impl Iterator<Item = char> + 'a {
+        dirs.iter().map(move |&dir| self.get(pos + dir).unwrap_or(default))
+    }
+
+    /// Number of in-bounds neighbours in 'dirs' whose characters satisfy 'pred'.
+    pub fn count_neighbors(&self, pos: Vec2, dirs: &[Vec2], pred: impl Fn(char) -> bool) -> usize {
+        self.grid.count_neighbors(pos, dirs, |&b| pred(b as char))
+    }
+
+    /// The first position of 'c', row by row.
+    pub fn find(&self, c: char) -> Option<Vec2> {
+        if !c.is_ascii() {
+            return None;
+        }
+        self.grid.find(&(c as u8))
+    }
+}
+
+/// Matrix holds only ASCII characters, one byte each.
+fn ascii(c: char) -> u8 {
+    if !c.is_ascii() {
+        panic!("Matrix can hold only ASCII characters, not '{}'", c);
+    }
+    c as u8
+}
+
+fn as_str(row: &[u8]) -> &str {
+    // Only ASCII is ever put into the grid.
+    std::str::from_utf8(row).unwrap()
+}
+
+pub fn read_matrix(filename: &str) -> crate::Result<Matrix> {
+    let lines = read_lines(filename)?;
+    Ok(read_matrix_from_lines(lines).map_err(|err| err.in_file(filename))?)
+}
+
+/// All the lines must have the same length as the first one.
+pub fn read_matrix_from_lines(lines: Vec<String>) -> ParseResult<Matrix> {
+    let grid = Grid::from_lines(&lines, |c| c.is_ascii().then_some(c as u8), "ASCII character")?;
+    Ok(Matrix { grid })
+}
+
+pub fn print_matrix(matrix: &Matrix, overrides: &HashMap<Vec2, char>) {
+    for y in 0..matrix.height {
+        for x in 0..matrix.width {
+            let pos = Vec2::new(x as i32, y as i32);
+            let val = if let Some(&c) = overrides.get(&pos) { c } else { matrix.get(pos).unwrap() };
+            print!("{}", val);
+        }
+        println!();
+    }
+}
+
+/// Collects locations of all points from 'marks'.
+/// Points in each Vec are in order top-to-bottom, left-to-right (0,0 is top-left).
+/// Optionally fills the found spots with provided 'fill' character.
+pub fn find_points(matrix: &mut Matrix, fill: Option<char>, marks: &HashSet<char>) -> HashMap<char, Vec<Vec2>> {
+    let mut result: HashMap<char, Vec<Vec2>> = HashMap::new();
+    for (pos, &b) in matrix.grid.iter() {
+        let c = b as char;
+        if !marks.contains(&c) { continue; }
+        result.entry(c).or_default().push(pos);
+    }
+
+    if let Some(f) = fill {
+        for (_c, positions) in result.iter() {
+            for pos in positions {
+                matrix.put(*pos, f);
+            }
+        }
+    }
+    result
+}
+
+/// Collects locations of all points from 'marks'.
+/// Expects there to be at most one of each point type (exactly one if 'all' is true).
+/// Optionally fills the found spots with provided 'fill' character.
+pub fn find_single_points(matrix: &mut Matrix, fill: Option<char>, marks: &HashSet<char>, all: bool) -> ParseResult<HashMap<char, Vec2>> {
+    let pre_result = find_points(matrix, None, marks);
+    if all {
+        if let Some(missing) = marks.iter().filter(|c| !pre_result.contains_key(c)).min() {
+            return Err(ParseError::new(matrix.height, "", 0, format!("'{}' somewhere in the matrix", missing)));
+        }
+    }
+    let mut result: HashMap<char, Vec2> = HashMap::new();
+    for (c, positions) in pre_result {
+        if positions.len() != 1 {
+            let pos = positions[1];
+            let row = matrix.get_row(pos.y).unwrap();
+            return Err(ParseError::new(pos.y as usize, row, pos.x as usize, format!("only one '{}'", c)));
+        }
+        result.insert(c, positions[0]);
+    }
+
+    if let Some(f) = fill {
+        for pos in result.values() {
+            matrix.put(*pos, f);
+        }
+    }
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matrix(text: &[&str]) -> Matrix {
+        read_matrix_from_lines(text.iter().map(|line| line.to_string()).collect()).unwrap()
+    }
+
+    #[test]
+    fn access() {
+        let mut matrix = matrix(&["#.S", "E.#"]);
+        assert_eq!((matrix.width, matrix.height), (3, 2));
+        assert_eq!(matrix.get(Vec2::new(2, 0)), Some('S'));
+        assert_eq!(matrix.get(Vec2::new(3, 0)), None);
+        assert_eq!(matrix.get(Vec2::new(0, -1)), None);
+        assert_eq!(matrix.get_row(1), Some("E.#"));
+        assert_eq!(matrix.get_row(2), None);
+        assert_eq!(matrix.find('E'), Some(Vec2::new(0, 1)));
+        assert_eq!(matrix.find('é'), None);
+        assert_eq!(matrix.count('#'), 2);
+
+        matrix.put(Vec2::new(1, 1), '#');
+        assert_eq!(matrix.lines().collect::<Vec<_>>(), vec!["#.S", "E##"]);
+        assert_eq!(matrix.count_neighbors(Vec2::new(1, 0), &crate::grid::ORTHOGONAL, |c| c == '#'), 2);
+        assert_eq!(matrix.neighbor_chars(Vec2::new(0, 0), &crate::grid::ORTHOGONAL, ' ').collect::<String>(), " .E ");
+    }
+
+    #[test]
+    #[should_panic]
+    fn put_out_of_bounds() {
+        matrix(&["..", ".."]).put(Vec2::new(2, 0), '#');
+    }
+
+    #[test]
+    fn walls() {
+        let matrix = Matrix::new(4, 3, '.', Some('#'));
+        assert_eq!(matrix.lines().collect::<Vec<_>>(), vec!["####", "#..#", "####"]);
+        assert_eq!(matrix.to_string(), "Matrix (4 x 3):\n  ####\n  #..#\n  ####\n");
+    }
+
+    #[test]
+    fn non_ascii() {
+        let err = read_matrix_from_lines(vec!["..".to_string(), ".é".to_string()]).unwrap_err();
+        assert_eq!((err.line_idx, err.column, err.expected.as_str()), (1, 1, "ASCII character"));
+    }
+
+    #[test]
+    fn single_points() {
+        let marks = HashSet::from(['S', 'E']);
+        let mut start_end = matrix(&["S..", "..E"]);
+        let points = find_single_points(&mut start_end, Some('.'), &marks, true).unwrap();
+        assert_eq!(points, HashMap::from([('S', Vec2::new(0, 0)), ('E', Vec2::new(2, 1))]));
+        assert_eq!(start_end.count('.'), 6);
+
+        let err = find_single_points(&mut matrix(&["S..", "..S"]), None, &marks, false).unwrap_err();
+        assert_eq!((err.line_idx, err.column, err.expected.as_str()), (1, 2, "only one 'S'"));
+        let err = find_single_points(&mut matrix(&["S..", "..."]), None, &marks, true).unwrap_err();
+        assert_eq!((err.line_idx, err.expected.as_str()), (2, "'E' somewhere in the matrix"));
+    }
+}
@@ -0,0 +1,159 @@
+use crate::matrix::{print_matrix, Matrix};
+use crate::parse_error::{ParseError, ParseResult};
+use crate::search::{dijkstra, dijkstra_all, SearchResult, ShortestPaths};
+use crate::vec2::Vec2;
+
+#[derive(Debug)]
+pub struct Maze {
+    pub matrix: Matrix,
+    pub start: Vec2,
+}
+
+/// Finds the start position (marked with 'start_char') and replaces it with '.'.
+pub fn make_maze(mut matrix: Matrix, start_char: char) -> ParseResult<Maze> {
+    let Some(start) = matrix.find(start_char) else {
+        return Err(ParseError::new(matrix.height, "", 0, format!("start position '{}' somewhere in the maze", start_char)));
+    };
+    matrix.put(start, '.');
+    Ok(Maze { matrix, start })
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum Direction {
+    Up,         // Towards smaller y.
+    Right,      // Towards larger x.
+    Down,       // Towards larger y.
+    Left,       // Towards smaller x.
+}
+
+impl Direction {
+    pub fn turn_cw(&self) -> Self {
+        use Direction::*;
+        match *self {
+            Up => Right,
+            Right => Down,
+            Down => Left,
+            Left => Up,
+        }
+    }
+
+    pub fn turn_ccw(&self) -> Self {
+        use Direction::*;
+        match *self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    pub fn dir(&self) -> Vec2 {
+        use Direction::*;
+        match *self {
+            Up => Vec2::new(0, -1),
+            Right => Vec2::new(1, 0),
+            Down => Vec2::new(0, 1),
+            Left => Vec2::new(-1, 0),
+        }
+    }
+}
+
+pub fn print_maze(maze: &Maze, start_char: char) {
+    print_matrix(&maze.matrix, &hashmap! { maze.start => start_char })
+}
+
+/// Position together with the direction one is facing.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct Pose {
+    pub pos: Vec2,
+    pub dir: Direction,
+}
+
+/// Costs of the moves of 'OrientedWalker'. Turns happen in place, None means the turn is not allowed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalkCosts {
+    /// Step forward, in the facing direction.
+    pub step: i64,
+    /// Quarter turn counter-clockwise.
+    pub turn_left: Option<i64>,
+    /// Quarter turn clockwise.
+    pub turn_right: Option<i64>,
+    pub u_turn: Option<i64>,
+}
+
+/// Walks the maze from its start, facing some direction, with given costs of steps and turns.
+/// Walls are '#', so are positions outside of the maze.
+pub struct OrientedWalker<'a> {
+    pub maze: &'a Maze,
+    pub costs: WalkCosts,
+}
+
+impl<'a> OrientedWalker<'a> {
+    pub fn new(maze: &'a Maze, costs: WalkCosts) -> Self {
+        OrientedWalker { maze, costs }
+    }
+
+    /// Moves possible from 'pose' with their costs.
+    pub fn successors(&self, pose: &Pose) -> Vec<(Pose, i64)> {
+        let Pose { pos, dir } = *pose;
+        let mut result = Vec::new();
+        let next_pos = pos + dir.dir();
+        if self.maze.matrix.get(next_pos).is_some_and(|c| c != '#') {
+            result.push((Pose { pos: next_pos, dir }, self.costs.step));
+        }
+        let turns = [
+            (dir.turn_ccw(), self.costs.turn_left),
+            (dir.turn_cw(), self.costs.turn_right),
+            (dir.turn_cw().turn_cw(), self.costs.u_turn),
+        ];
+        for (next_dir, cost) in turns {
+            if let Some(cost) = cost {
+                result.push((Pose { pos, dir: next_dir }, cost));
+            }
+        }
+        result
+    }
+
+    /// Cheapest walk from the start facing 'start_dir' to a position satisfying 'is_goal'.
+    pub fn shortest(&self, start_dir: Direction, is_goal: impl Fn(Vec2) -> bool) -> SearchResult<Pose, i64> {
+        let start = Pose { pos: self.maze.start, dir: start_dir };
+        dijkstra([start], |pose| self.successors(pose), |pose| is_goal(pose.pos))
+    }
+
+    /// All the cheapest walks from the start facing 'start_dir' to a position satisfying 'is_goal'.
+    pub fn shortest_all(&self, start_dir: Direction, is_goal: impl Fn(Vec2) -> bool) -> ShortestPaths<Pose, i64> {
+        let start = Pose { pos: self.maze.start, dir: start_dir };
+        dijkstra_all([start], |pose| self.successors(pose), |pose| is_goal(pose.pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::matrix::read_matrix_from_lines;
+
+    fn maze() -> Maze {
+        let lines = ["#####", "#...#", "#S#E#", "#####"].map(String::from).to_vec();
+        make_maze(read_matrix_from_lines(lines).unwrap(), 'S').unwrap()
+    }
+
+    #[test]
+    fn disallowed_turns() {
+        let maze = maze();
+        let is_end = |pos| pos == Vec2::new(3, 2);
+        let both = WalkCosts { step: 1, turn_left: Some(10), turn_right: Some(10), u_turn: None };
+        let walker = OrientedWalker::new(&maze, both);
+        assert_eq!(walker.shortest(Direction::Up, is_end).goal_dist(), Some(24));
+
+        // Each right turn takes three left ones.
+        let left_only = WalkCosts { turn_right: None, ..both };
+        let walker = OrientedWalker::new(&maze, left_only);
+        let start = Pose { pos: maze.start, dir: Direction::Up };
+        assert_eq!(walker.successors(&start), vec![
+            (Pose { pos: Vec2::new(1, 1), dir: Direction::Up }, 1),
+            (Pose { pos: maze.start, dir: Direction::Left }, 10),
+        ]);
+        assert_eq!(walker.shortest(Direction::Up, is_end).goal_dist(), Some(64));
+        assert_eq!(walker.shortest_all(Direction::Up, is_end).count_paths(), 1);
+    }
+}
@@ -0,0 +1,106 @@
+use crate::find_union::FindUnion;
+use crate::grid::Grid;
+use crate::vec2::Vec2;
+
+/// Index into 'walls' of the wall whose insertion disconnects 'end' from 'start', walls being inserted in order.
+/// Cells where 'is_open' is false are walls from the very beginning. None if 'end' stays reachable all the time.
+///
+/// Works backwards in time: starts with all the walls in place and removes them one by one, joining each freed cell
+/// with its open neighbours, until 'start' and 'end' get connected. Every cell is thus visited only once.
+pub fn first_blocking_wall<T>(grid: &Grid<T>, is_open: impl Fn(&T) -> bool, walls: &[Vec2], start: Vec2, end: Vec2) -> Option<usize> {
+    // A cell is blocked from the first time a wall falls on it. Walls outside of the grid change nothing.
+    let mut inserted_at: Grid<Option<usize>> = grid.map(|_| None);
+    for (idx, &wall) in walls.iter().enumerate() {
+        if let Some(cell @ None) = inserted_at.get_mut(wall) {
+            *cell = Some(idx);
+        }
+    }
+
+    let mut open = grid.map(&is_open);
+    for (pos, cell) in inserted_at.iter() {
+        if cell.is_some() {
+            open[pos] = false;
+        }
+    }
+
+    let mut sets = FindUnion::new();
+    for pos in grid.positions() {
+        if open[pos] {
+            join_open_neighbors(&mut sets, &open, pos);
+        }
+    }
+    if connected(&mut sets, &open, start, end) {
+        return None;
+    }
+
+    for (idx, &wall) in walls.iter().enumerate().rev() {
+        if inserted_at.get(wall) != Some(&Some(idx)) || !grid.get(wall).is_some_and(&is_open) {
+            continue;
+        }
+        open[wall] = true;
+        join_open_neighbors(&mut sets, &open, wall);
+        if connected(&mut sets, &open, start, end) {
+            return Some(idx);
+        }
+    }
+    None
+}
+
+fn join_open_neighbors(sets: &mut FindUnion<Vec2>, open: &Grid<bool>, pos: Vec2) {
+    sets.ensure(pos);
+    for next in open.neighbors4(pos) {
+        if open[next] {
+            sets.join(pos, next);
+        }
+    }
+}
+
+fn connected(sets: &mut FindUnion<Vec2>, open: &Grid<bool>, start: Vec2, end: Vec2) -> bool {
+    open.get(start) == Some(&true) && open.get(end) == Some(&true) && sets.connected(start, end)
+}
+
+/// Smallest 'n' in 0..len for which 'holds(n)' is false, by binary search. None if it holds for all of them.
+/// 'holds' must be monotone: once it is false, it stays false for all larger 'n' (e.g. "exit is reachable
+/// after the first 'n' + 1 walls are inserted"). Useful when the check is not a plain grid reachability.
+pub fn first_failure(len: usize, mut holds: impl FnMut(usize) -> bool) -> Option<usize> {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if holds(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    if low < len { Some(low) } else { None }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::bfs;
+
+    #[test]
+    fn binary_search() {
+        assert_eq!(first_failure(10, |n| n < 7), Some(7));
+        assert_eq!(first_failure(10, |_| false), Some(0));
+        assert_eq!(first_failure(10, |_| true), None);
+        assert_eq!(first_failure(0, |_| false), None);
+    }
+
+    #[test]
+    fn failure_matches_blocking_wall() {
+        let grid = Grid::new(3, 3, '.');
+        // The wall outside of the grid and the repeated one change nothing, (1, 0) cuts the last way through.
+        let walls = [(1, 2), (5, 5), (1, 1), (1, 2), (1, 0), (2, 2)].map(|(x, y)| Vec2::new(x, y));
+        let (start, end) = (Vec2::new(0, 0), Vec2::new(2, 2));
+        let reachable = |n: usize| {
+            let walls = &walls[..=n];
+            let successors = |&pos: &Vec2| grid.neighbors4(pos).filter(|next| !walls.contains(next)).collect::<Vec<_>>();
+            bfs([start], successors, |&pos| pos == end).goal.is_some()
+        };
+        assert_eq!(first_failure(walls.len(), reachable), Some(4));
+        assert_eq!(first_blocking_wall(&grid, |&c| c == '.', &walls, start, end), Some(4));
+        assert_eq!(first_blocking_wall(&grid, |&c| c == '.', &walls[..4], start, end), None);
+    }
+}
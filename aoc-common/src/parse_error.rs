@@ -0,0 +1,92 @@
+use std::any::type_name;
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Error of parsing the puzzle input. Points at the bad spot of the offending line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Name of the input file. Parsers only see the lines, so it is filled in later (see 'in_file').
+    pub file: Option<String>,
+    /// Zero-based index of the offending line. Equals number of lines if the input ended too early.
+    pub line_idx: usize,
+    /// Zero-based column (in characters) of the bad spot.
+    pub column: usize,
+    /// The whole offending line. Empty if the input ended too early.
+    pub text: String,
+    /// What was expected at the bad spot, e.g. "number" or "'L' or 'R'".
+    pub expected: String,
+}
+
+pub type ParseResult<T> = std::result::Result<T, ParseError>;
+
+impl ParseError {
+    pub fn new(line_idx: usize, text: &str, column: usize, expected: impl Into<String>) -> Self {
+        Self { file: None, line_idx, column, text: text.to_string(), expected: expected.into() }
+    }
+
+    /// Error at the start of the line, for lines that are wrong as a whole.
+    pub fn line(line_idx: usize, text: &str, expected: impl Into<String>) -> Self {
+        Self::new(line_idx, text, 0, expected)
+    }
+
+    /// Error at 'token', which must be a slice of 'line' (a regex capture, a piece of split, the rest left by nom, ...).
+    /// The column is where the token starts.
+    pub fn at(line_idx: usize, line: &str, token: &str, expected: impl Into<String>) -> Self {
+        Self::new(line_idx, line, column_of(line, token), expected)
+    }
+
+    /// The input ended before 'expected' was found.
+    pub fn end_of_input(lines: &[String], expected: impl Into<String>) -> Self {
+        Self::new(lines.len(), "", 0, expected)
+    }
+
+    /// Error reported by nom while parsing 'line'. Points at the input nom was not able to consume.
+    pub fn from_nom(line_idx: usize, line: &str, err: nom::Err<nom::error::Error<&str>>, expected: impl Into<String>) -> Self {
+        match err {
+            nom::Err::Error(e) | nom::Err::Failure(e) => Self::at(line_idx, line, e.input, expected),
+            nom::Err::Incomplete(_) => Self::new(line_idx, line, line.chars().count(), expected),
+        }
+    }
+
+    pub fn in_file(mut self, file: impl Into<String>) -> Self {
+        self.file = Some(file.into());
+        self
+    }
+}
+
+/// Column (in characters) where 'token' starts inside 'line'. Zero if 'token' is not a slice of 'line'.
+fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let pos = token.as_ptr() as usize;
+    if pos < start || pos > start + line.len() {
+        return 0;
+    }
+    line[..pos - start].chars().count()
+}
+
+/// Parses 'token' (a slice of 'line') as T. On failure points at the token.
+pub fn parse_token<T: FromStr>(line_idx: usize, line: &str, token: &str) -> ParseResult<T> {
+    token.parse::<T>().map_err(|_| ParseError::at(line_idx, line, token, type_name::<T>()))
+}
+
+impl fmt::Display for ParseError {
+    /// Renders e.g.:
+    /// dec1.in.txt:3:4: expected i64
+    ///   R12x
+    ///      ^
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let location = match &self.file {
+            Some(file) => format!("{}:{}:{}", file, self.line_idx + 1, self.column + 1),
+            None => format!("line {}, column {}", self.line_idx + 1, self.column + 1),
+        };
+        if self.text.is_empty() {
+            return write!(f, "{}: expected {}, but found nothing", location, self.expected);
+        }
+        writeln!(f, "{}: expected {}", location, self.expected)?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {}^", " ".repeat(self.column))
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,126 @@
+use crate::grid::{Grid, ORTHOGONAL};
+use crate::vec2::Vec2;
+
+/// Connected component of orthogonally adjacent cells holding the same value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region<T> {
+    pub value: T,
+    /// Some cell of the region, the first one row by row.
+    pub start: Vec2,
+    /// Number of cells.
+    pub area: usize,
+    /// Number of unit fence segments between the region and anything else (other cells or the outside).
+    pub perimeter: usize,
+    /// Number of straight fences, each made of one or more segments in a row.
+    pub sides: usize,
+    /// Number of corners, convex and concave. Equals 'sides', since every side goes from corner to corner.
+    pub corners: usize,
+    /// Top-left and bottom-right cell of the bounding box (both inclusive).
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+/// All the regions of a grid.
+#[derive(Debug, Clone)]
+pub struct Regions<T> {
+    /// Index into 'regions' for every cell.
+    pub labels: Grid<usize>,
+    pub regions: Vec<Region<T>>,
+}
+
+impl<T> Regions<T> {
+    /// The region 'pos' belongs to. Panics if 'pos' is out of bounds.
+    pub fn region_at(&self, pos: Vec2) -> &Region<T> {
+        &self.regions[self.labels[pos]]
+    }
+}
+
+/// Labels the connected components of equal cells. Uses an explicit stack, so even huge regions are fine.
+pub fn find_regions<T: PartialEq + Clone>(grid: &Grid<T>) -> Regions<T> {
+    let mut labels: Grid<Option<usize>> = grid.map(|_| None);
+    let mut count = 0;
+    let mut stack = Vec::new();
+    for start in grid.positions() {
+        if labels[start].is_some() {
+            continue;
+        }
+        labels[start] = Some(count);
+        stack.push(start);
+        while let Some(pos) = stack.pop() {
+            for next in grid.neighbors4(pos) {
+                if labels[next].is_none() && grid[next] == grid[start] {
+                    labels[next] = Some(count);
+                    stack.push(next);
+                }
+            }
+        }
+        count += 1;
+    }
+
+    let labels = labels.map(|label| label.unwrap());
+    let regions = measure(grid, &labels, count);
+    Regions { labels, regions }
+}
+
+fn measure<T: Clone>(grid: &Grid<T>, labels: &Grid<usize>, count: usize) -> Vec<Region<T>> {
+    let mut regions: Vec<Option<Region<T>>> = vec![None; count];
+    for (pos, &label) in labels.iter() {
+        let same = |p: Vec2| labels.get(p) == Some(&label);
+        let region = regions[label].get_or_insert_with(|| Region {
+            value: grid[pos].clone(), start: pos, area: 0, perimeter: 0, sides: 0, corners: 0, min: pos, max: pos,
+        });
+
+        region.area += 1;
+        region.min = Vec2::new(region.min.x.min(pos.x), region.min.y.min(pos.y));
+        region.max = Vec2::new(region.max.x.max(pos.x), region.max.y.max(pos.y));
+        for &dir in &ORTHOGONAL {
+            if same(pos + dir) {
+                continue;
+            }
+            region.perimeter += 1;
+            // Each side is counted at its first segment: the one whose neighbour along the fence does not continue it.
+            let along = pos + dir.rot_ccw();
+            if !same(along) || same(along + dir) {
+                region.sides += 1;
+            }
+        }
+        for (&a, &b) in ORTHOGONAL.iter().zip(ORTHOGONAL.iter().cycle().skip(1)) {
+            let convex = !same(pos + a) && !same(pos + b);
+            let concave = same(pos + a) && same(pos + b) && !same(pos + a + b);
+            if convex || concave {
+                region.corners += 1;
+            }
+        }
+    }
+    regions.into_iter().map(|region| region.unwrap()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions_touching_diagonally() {
+        let lines = ["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"].map(String::from);
+        let grid = Grid::from_lines(&lines, Some, "letter").unwrap();
+        let regions = find_regions(&grid);
+        assert_eq!(regions.regions.len(), 3);
+        for region in &regions.regions {
+            assert_eq!(region.sides, region.corners);
+        }
+
+        let a = regions.region_at(Vec2::new(5, 5));
+        assert_eq!((a.value, a.start, a.area, a.perimeter, a.sides), ('A', Vec2::new(0, 0), 28, 40, 12));
+        assert_eq!((a.min, a.max), (Vec2::new(0, 0), Vec2::new(5, 5)));
+
+        let b = regions.region_at(Vec2::new(4, 2));
+        assert_eq!((b.value, b.start, b.area, b.perimeter, b.sides), ('B', Vec2::new(3, 1), 4, 8, 4));
+        assert_eq!((b.min, b.max), (Vec2::new(3, 1), Vec2::new(4, 2)));
+
+        let other_b = regions.region_at(Vec2::new(1, 3));
+        assert_ne!(other_b, b);
+        assert_eq!((other_b.area, other_b.sides), (4, 4));
+        assert_eq!((other_b.min, other_b.max), (Vec2::new(1, 3), Vec2::new(2, 4)));
+        assert_eq!(regions.labels[Vec2::new(2, 4)], regions.labels[Vec2::new(1, 3)]);
+    }
+}
@@ -0,0 +1,225 @@
+use std::any::Any;
+use std::panic;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use clap::{Args, Parser, Subcommand};
+use crate::basic_parsing::read_lines;
+use crate::inputs::{input_filename, locate_input};
+use crate::solver::DynSolver;
+use crate::timing;
+use crate::Result;
+
+/// Registry entry: solver of given day.
+#[derive(Clone, Copy)]
+pub struct Day {
+    pub day: u32,
+    pub solver: &'static dyn DynSolver,
+}
+
+#[derive(Debug, Parser)]
+#[command(about = "Advent of Code solutions.")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Runs one day (or all registered days).
+    Run(RunArgs),
+    /// Measures parsing and each part of every day (or of one day) and prints a table.
+    Time(TimeArgs),
+    /// Lists all registered solutions.
+    List,
+    /// Runs the playground.
+    Play,
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// Day to run.
+    #[arg(short, long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u32>,
+
+    /// Part to run. Both parts are run if not given.
+    #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: Option<u32>,
+
+    /// Input variant: 'in' reads decN.in.txt, 'ex' reads decN.ex.txt, 'ex2' reads decN.ex2.txt, etc.
+    /// Files are searched in the current directory and in the crate's src directory, or only in $AOC_INPUT_DIR if set.
+    #[arg(short, long, default_value = "in")]
+    input: String,
+
+    /// Runs every registered solution in sequence.
+    #[arg(short, long)]
+    all: bool,
+
+    /// Also writes the graph of the input to this file in Graphviz DOT format, for days that have one.
+    #[arg(long, conflicts_with = "all")]
+    dot: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+struct TimeArgs {
+    /// Day to measure. All registered days are measured if not given.
+    #[arg(short, long)]
+    day: Option<u32>,
+
+    /// Input variant, as for 'run'.
+    #[arg(short, long, default_value = "in")]
+    input: String,
+
+    /// Also writes the measurements as JSON to this file ('-' writes only the JSON to stdout).
+    #[arg(long)]
+    json: Option<PathBuf>,
+}
+
+/// Solutions of one year, with the directory where the year keeps its inputs.
+pub struct Year {
+    pub days: &'static [Day],
+    pub input_dir: &'static str,
+    pub play: fn(),
+}
+
+/// Reads the input of given day.
+pub(crate) fn read_input(year: &Year, day: &Day, input: &str) -> Result<Vec<String>> {
+    read_lines(locate_input(Path::new(year.input_dir), day.day, input)?)
+}
+
+/// Parses the lines of input 'variant'. Parse errors point into its file. Panics are caught and reported as errors.
+pub(crate) fn parse_input(day: &Day, lines: &[String], variant: &str) -> std::result::Result<Box<dyn Any>, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| day.solver.parse_any(lines, variant))) {
+        Ok(Ok(parsed)) => Ok(parsed),
+        Ok(Err(err)) => Err(err.in_file(input_filename(day.day, variant)).to_string()),
+        Err(_) => Err("panicked while parsing".to_string()),
+    }
+}
+
+/// Solves one part from the parsed input. Panics are caught and reported as errors.
+pub(crate) fn solve_part(day: &Day, parsed: &dyn Any, part: u32) -> std::result::Result<String, String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| day.solver.solve_any(parsed, part))) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(err)) => Err(err.to_string()),
+        Err(_) => Err("panicked".to_string()),
+    }
+}
+
+/// Writes the DOT file of the parsed input. Panics are caught and reported as errors.
+fn write_dot(day: &Day, parsed: &dyn Any, path: &Path) -> std::result::Result<(), String> {
+    match panic::catch_unwind(AssertUnwindSafe(|| day.solver.dot_any(parsed)?.write(path))) {
+        Ok(result) => result.map_err(|err| err.to_string()),
+        Err(_) => Err("panicked".to_string()),
+    }
+}
+
+/// Runs given parts of a single day, and writes the DOT file if 'dot' is given. Input is parsed once for all of it.
+/// Returns number of parts that failed, the DOT file counting as one more part.
+fn run_day(year: &Year, day: &Day, parts: &[u32], input: &str, dot: Option<&Path>) -> usize {
+    let filename = input_filename(day.day, input);
+    let parsed = read_input(year, day, input)
+        .map_err(|err| err.to_string())
+        .and_then(|lines| parse_input(day, &lines, input));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("Day {} ({}) failed: {}", day.day, filename, err);
+            return parts.len() + usize::from(dot.is_some());
+        },
+    };
+
+    let mut failed = 0;
+    if let Some(path) = dot {
+        match write_dot(day, parsed.as_ref(), path) {
+            Ok(()) => println!("Day {} ({}): graph written to {}", day.day, filename, path.display()),
+            Err(err) => {
+                eprintln!("Day {} ({}) graph failed: {}", day.day, filename, err);
+                failed += 1;
+            },
+        }
+    }
+    for &part in parts {
+        match solve_part(day, parsed.as_ref(), part) {
+            Ok(answer) => println!("Day {} part {} ({}): {}", day.day, part, filename, answer),
+            Err(err) => {
+                eprintln!("Day {} part {} ({}) failed: {}", day.day, part, filename, err);
+                failed += 1;
+            },
+        }
+    }
+    failed
+}
+
+fn run(year: &Year, args: &RunArgs) -> ExitCode {
+    let selected: Vec<(&Day, Vec<u32>)> = year.days.iter()
+        .filter(|d| args.all || Some(d.day) == args.day)
+        .map(|d| (d, (1..=d.solver.parts()).filter(|&p| args.part.is_none() || Some(p) == args.part).collect()))
+        .filter(|(_d, parts): &(&Day, Vec<u32>)| !parts.is_empty())
+        .collect();
+
+    if selected.is_empty() {
+        let day = args.day.map_or("any".to_string(), |d| d.to_string());
+        let part = args.part.map_or("any".to_string(), |p| p.to_string());
+        eprintln!("No solution registered for day {} part {}.", day, part);
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = 0;
+    let mut total = 0;
+    for (day, parts) in &selected {
+        failed += run_day(year, day, parts, &args.input, args.dot.as_deref());
+        total += parts.len() + usize::from(args.dot.is_some());
+    }
+
+    if failed > 0 {
+        eprintln!("{} of {} solutions failed.", failed, total);
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+fn time(year: &Year, args: &TimeArgs) -> ExitCode {
+    let days: Vec<&Day> = year.days.iter().filter(|d| args.day.is_none() || Some(d.day) == args.day).collect();
+    if days.is_empty() {
+        eprintln!("No solution registered for day {}.", args.day.unwrap_or_default());
+        return ExitCode::FAILURE;
+    }
+
+    let report = timing::measure(year, &days, &args.input);
+    let to_stdout = args.json.as_deref() == Some(Path::new("-"));
+    if !to_stdout {
+        timing::print_table(&report);
+    }
+    if let Some(path) = &args.json {
+        if let Err(err) = timing::write_json(&report, path) {
+            eprintln!("Cannot write timings: {}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if report.failed() > 0 {
+        return ExitCode::FAILURE;
+    }
+    ExitCode::SUCCESS
+}
+
+/// Parses the command line and dispatches to the registered solutions.
+pub fn main(year: &Year) -> ExitCode {
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Run(args) => run(year, &args),
+        Command::Time(args) => time(year, &args),
+        Command::List => {
+            for day in year.days {
+                for part in 1..=day.solver.parts() {
+                    println!("day {} part {}", day.day, part);
+                }
+            }
+            ExitCode::SUCCESS
+        },
+        Command::Play => {
+            (year.play)();
+            ExitCode::SUCCESS
+        },
+    }
+}
@@ -0,0 +1,335 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+use priority_queue::DoublePriorityQueue;
+
+/// What a search found: costs of the reached states and how they were reached.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S, C> {
+    /// Cost of the cheapest found path from a start to each reached state.
+    /// If the search stopped at a goal, states still waiting in the queue may hold only upper bounds.
+    pub dist: HashMap<S, C>,
+    /// The state each reached state was reached from on its cheapest path. Starts have none.
+    pub prev: HashMap<S, S>,
+    /// The goal state where the search stopped, if any was reached.
+    pub goal: Option<S>,
+}
+
+impl<S: Hash + Eq + Clone, C: Copy> SearchResult<S, C> {
+    fn new() -> Self {
+        SearchResult { dist: HashMap::new(), prev: HashMap::new(), goal: None }
+    }
+
+    pub fn goal_dist(&self) -> Option<C> {
+        self.goal.as_ref().map(|goal| self.dist[goal])
+    }
+
+    /// States of the cheapest path from a start to 'to', both included. None if 'to' was not reached.
+    pub fn path(&self, to: &S) -> Option<Vec<S>> {
+        if !self.dist.contains_key(to) {
+            return None;
+        }
+        let mut path = vec![to.clone()];
+        while let Some(prev) = self.prev.get(path.last().unwrap()) {
+            path.push(prev.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|goal| self.path(goal))
+    }
+}
+
+/// Breadth-first search, every step costs 1. Stops at the first state satisfying 'is_goal',
+/// pass '|_| false' to reach everything reachable.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, usize>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if !result.dist.contains_key(&start) {
+            result.dist.insert(start.clone(), 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        let dist = result.dist[&state];
+        for next in successors(&state) {
+            if result.dist.contains_key(&next) {
+                continue;
+            }
+            result.dist.insert(next.clone(), dist + 1);
+            result.prev.insert(next.clone(), state.clone());
+            queue.push_back(next);
+        }
+    }
+    result
+}
+
+/// Dijkstra's search. 'successors' returns the next states together with (non-negative) costs of getting there.
+/// Stops at the first state satisfying 'is_goal', which is then one of the cheapest to reach.
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// A* search. 'heuristic' estimates the remaining cost to a goal, it must never overestimate it
+/// and must not drop by more than the cost of a step (so that each state is expanded only once).
+/// Cost zero is 'C::default()'.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut result = SearchResult::new();
+    // Priority is the cost so far plus the estimate of the rest.
+    let mut queue = DoublePriorityQueue::new();
+    for start in starts {
+        let estimate = heuristic(&start);
+        result.dist.insert(start.clone(), C::default());
+        queue.push(start, estimate);
+    }
+
+    while let Some((state, _)) = queue.pop_min() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+        let dist = result.dist[&state];
+        for (next, step) in successors(&state) {
+            let next_dist = dist + step;
+            if result.dist.get(&next).is_some_and(|&known| known <= next_dist) {
+                continue;
+            }
+            let estimate = next_dist + heuristic(&next);
+            result.dist.insert(next.clone(), next_dist);
+            result.prev.insert(next.clone(), state.clone());
+            queue.push_decrease(next, estimate);
+        }
+    }
+    result
+}
+
+/// All the cheapest paths from the starts to the cheapest goals, as a DAG of predecessors.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<S, C> {
+    /// Cost of the cheapest path to each reached state. Only states not costlier than the goals are final.
+    pub dist: HashMap<S, C>,
+    /// All the states each state is reached from on one of its cheapest paths. Starts have none.
+    pub preds: HashMap<S, Vec<S>>,
+    /// Goal states reached at the lowest cost, ties included. Empty if no goal was reached.
+    pub goals: Vec<S>,
+    /// Expanded states in order of expansion, so every state comes after all of its predecessors.
+    order: Vec<S>,
+}
+
+impl<S: Hash + Eq + Clone, C: Copy> ShortestPaths<S, C> {
+    pub fn goal_dist(&self) -> Option<C> {
+        self.goals.first().map(|goal| self.dist[goal])
+    }
+
+    fn preds_of(&self, state: &S) -> &[S] {
+        self.preds.get(state).map_or(&[], |preds| preds.as_slice())
+    }
+
+    /// Number of distinct cheapest paths from a start to 'to'.
+    pub fn count_paths_to(&self, to: &S) -> u64 {
+        let mut counts: HashMap<&S, u64> = HashMap::new();
+        for state in self.order.iter().chain(std::iter::once(to)) {
+            let preds = self.preds_of(state);
+            let count = if preds.is_empty() { 1 } else { preds.iter().map(|p| counts.get(p).copied().unwrap_or(0)).sum() };
+            counts.insert(state, count);
+        }
+        if self.dist.contains_key(to) { counts[to] } else { 0 }
+    }
+
+    /// Number of distinct cheapest paths from a start to any of the goals.
+    pub fn count_paths(&self) -> u64 {
+        self.goals.iter().map(|goal| self.count_paths_to(goal)).sum()
+    }
+
+    /// Every cheapest path to a goal, from a start to the goal. Paths are built lazily, as there may be very many.
+    pub fn paths(&self) -> Paths<'_, S> {
+        Paths { preds: &self.preds, goals: self.goals.iter(), stack: Vec::new() }
+    }
+
+    /// All the states lying on at least one cheapest path to a goal.
+    pub fn states_on_paths(&self) -> HashSet<S> {
+        let mut result: HashSet<S> = self.goals.iter().cloned().collect();
+        let mut stack: Vec<&S> = self.goals.iter().collect();
+        while let Some(state) = stack.pop() {
+            for pred in self.preds_of(state) {
+                if result.insert(pred.clone()) {
+                    stack.push(pred);
+                }
+            }
+        }
+        result
+    }
+}
+
+/// Lazy iterator over the cheapest paths, see 'ShortestPaths::paths'.
+pub struct Paths<'a, S> {
+    preds: &'a HashMap<S, Vec<S>>,
+    goals: std::slice::Iter<'a, S>,
+    /// Path walked back from a goal so far, with index of the next predecessor to try at each state.
+    stack: Vec<(&'a S, usize)>,
+}
+
+impl<S: Hash + Eq + Clone> Iterator for Paths<'_, S> {
+    type Item = Vec<S>;
+
+    fn next(&mut self) -> Option<Vec<S>> {
+        loop {
+            if self.stack.is_empty() {
+                self.stack.push((self.goals.next()?, 0));
+            }
+            let (state, idx) = *self.stack.last().unwrap();
+            let preds = self.preds.get(state).map_or(&[][..], |preds| preds.as_slice());
+            if preds.is_empty() {
+                // Walked back to a start.
+                let path = self.stack.iter().rev().map(|&(s, _)| s.clone()).collect();
+                self.stack.pop();
+                return Some(path);
+            }
+            if idx < preds.len() {
+                self.stack.last_mut().unwrap().1 += 1;
+                self.stack.push((&preds[idx], 0));
+            } else {
+                self.stack.pop();
+            }
+        }
+    }
+}
+
+/// Dijkstra's search keeping all the cheapest paths, not just one. Runs until all the goals
+/// of the lowest cost are found (or everything is reached, if there are no goals).
+/// Goals are not expanded further.
+/// Panics on a step that does not cost more than zero: a state could then be reached again after it was expanded,
+/// and 'order' would not be topological.
+pub fn dijkstra_all<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S, C>
+where
+    S: Hash + Eq + Clone,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut result = ShortestPaths { dist: HashMap::new(), preds: HashMap::new(), goals: Vec::new(), order: Vec::new() };
+    let mut queue = DoublePriorityQueue::new();
+    for start in starts {
+        result.dist.insert(start.clone(), C::default());
+        queue.push(start, C::default());
+    }
+
+    while let Some((state, dist)) = queue.pop_min() {
+        if result.goal_dist().is_some_and(|goal_dist| goal_dist < dist) {
+            break;
+        }
+        result.order.push(state.clone());
+        if is_goal(&state) {
+            result.goals.push(state);
+            continue;
+        }
+        for (next, step) in successors(&state) {
+            assert!(step > C::default(), "dijkstra_all needs steps of positive cost");
+            let next_dist = dist + step;
+            match result.dist.get(&next) {
+                Some(&known) if known < next_dist => continue,
+                Some(&known) if known == next_dist => {
+                    // The same successor may be listed twice, its path must not be counted twice.
+                    let preds = result.preds.entry(next).or_default();
+                    if !preds.contains(&state) {
+                        preds.push(state.clone());
+                    }
+                    continue;
+                },
+                _ => {},
+            }
+            result.dist.insert(next.clone(), next_dist);
+            result.preds.insert(next.clone(), vec![state.clone()]);
+            queue.push_decrease(next, next_dist);
+        }
+    }
+    result
+}
+
+/// 'dijkstra_all' where every step costs 1.
+pub fn bfs_all<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S, usize>
+where
+    S: Hash + Eq + Clone,
+    I: IntoIterator<Item = S>,
+{
+    dijkstra_all(starts, |state| successors(state).into_iter().map(|next| (next, 1)), is_goal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -> b -> d -> e and a -> c -> d -> e cost 3, a -> e costs 5. b lists d twice.
+    fn diamond(state: &char) -> Vec<(char, u32)> {
+        match state {
+            'a' => vec![('b', 1), ('c', 1), ('e', 5)],
+            'b' => vec![('d', 1), ('d', 1)],
+            'c' => vec![('d', 1)],
+            'd' => vec![('e', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn all_cheapest_paths() {
+        let result = dijkstra_all(['a'], diamond, |&state| state == 'e');
+        assert_eq!(result.goals, vec!['e']);
+        assert_eq!(result.goal_dist(), Some(3));
+        assert_eq!(result.count_paths(), 2);
+        assert_eq!(result.count_paths_to(&'d'), 2);
+        assert_eq!(result.count_paths_to(&'x'), 0);
+
+        let mut paths: Vec<String> = result.paths().map(|path| path.into_iter().collect()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["abde", "acde"]);
+        assert_eq!(result.states_on_paths(), HashSet::from(['a', 'b', 'c', 'd', 'e']));
+    }
+
+    #[test]
+    #[should_panic(expected = "positive cost")]
+    fn zero_cost_step() {
+        dijkstra_all(['a'], |&state| if state == 'a' { vec![('b', 0)] } else { vec![] }, |&state| state == 'b');
+    }
+}
@@ -0,0 +1,75 @@
+use std::any::Any;
+use std::fmt::Display;
+use crate::dot::Dot;
+use crate::parse_error::ParseResult;
+use crate::Result;
+
+/// A single day of the puzzle.
+/// Input is parsed once, and then both parts are solved from the parsed input.
+pub trait Solver {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    /// Number of parts that are solved (1 or 2).
+    const PARTS: u32 = 2;
+
+    /// Malformed input is reported with the line and column where it went wrong.
+    fn parse(&self, lines: &[String]) -> ParseResult<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Self::Answer1>;
+    fn part2(&self, input: &Self::Input) -> Result<Self::Answer2>;
+
+    /// Parses input 'variant' ("in", "ex", ... see 'input_filename'). Days whose puzzle parameters are not written
+    /// in the input, but differ between the example and the puzzle input, pick them here.
+    fn parse_variant(&self, lines: &[String], _variant: &str) -> ParseResult<Self::Input> {
+        self.parse(lines)
+    }
+
+    /// Picture of the input for Graphviz, written by 'run --dot <file>'. Only days with a graph in the input have one.
+    fn dot(&self, _input: &Self::Input) -> Result<Dot> {
+        Err("There is no graph to draw for this day.".into())
+    }
+}
+
+/// Type-erased version of Solver, so that solvers of all days can be kept in one registry.
+/// Answers are returned as strings.
+pub trait DynSolver: Sync {
+    fn parts(&self) -> u32;
+    fn parse_any(&self, lines: &[String], variant: &str) -> ParseResult<Box<dyn Any>>;
+    fn solve_any(&self, input: &dyn Any, part: u32) -> Result<String>;
+    fn dot_any(&self, input: &dyn Any) -> Result<Dot>;
+}
+
+impl<S> DynSolver for S
+where
+    S: Solver + Sync,
+    S::Input: 'static,
+{
+    fn parts(&self) -> u32 {
+        S::PARTS
+    }
+
+    fn parse_any(&self, lines: &[String], variant: &str) -> ParseResult<Box<dyn Any>> {
+        Ok(Box::new(self.parse_variant(lines, variant)?))
+    }
+
+    fn solve_any(&self, input: &dyn Any, part: u32) -> Result<String> {
+        let input = input.downcast_ref::<S::Input>().ok_or("Input was not produced by this solver.")?;
+        match part {
+            1 => Ok(self.part1(input)?.to_string()),
+            2 if part <= S::PARTS => Ok(self.part2(input)?.to_string()),
+            _ => Err(format!("Part {} is not solved.", part).into()),
+        }
+    }
+
+    fn dot_any(&self, input: &dyn Any) -> Result<Dot> {
+        let input = input.downcast_ref::<S::Input>().ok_or("Input was not produced by this solver.")?;
+        self.dot(input)
+    }
+}
+
+/// Parses the lines of input 'variant' and solves given part.
+pub fn solve(solver: &dyn DynSolver, lines: &[String], variant: &str, part: u32) -> Result<String> {
+    let input = solver.parse_any(lines, variant)?;
+    solver.solve_any(input.as_ref(), part)
+}
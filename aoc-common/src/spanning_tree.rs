@@ -0,0 +1,149 @@
+use crate::find_union::DisjointSets;
+
+/// Undirected edge between nodes 'u' and 'v' (dense indices).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Edge<C> {
+    pub u: usize,
+    pub v: usize,
+    pub cost: C,
+}
+
+/// An edge that joined two components of the spanning forest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Merge<C> {
+    pub edge: Edge<C>,
+    /// Sizes of the components of 'u' and 'v' before the merge.
+    pub sizes: (usize, usize),
+    /// Number of components left after the merge. 1 means everything is connected.
+    pub components: usize,
+}
+
+/// Kruskal's algorithm as a stream of merges, cheapest edge first.
+/// '.nth(k - 1)' is the k-th merge, '.last()' the one that connected everything (if the graph is connected).
+/// Edges that join nodes already connected are skipped, 'sets' shows the components at any point.
+#[derive(Debug, Clone)]
+pub struct Kruskal<C> {
+    edges: std::vec::IntoIter<Edge<C>>,
+    sets: DisjointSets,
+}
+
+impl<C: Ord + Copy> Kruskal<C> {
+    /// Sorts the edges by cost (stable, so equal costs keep their order).
+    pub fn new(num_nodes: usize, edges: impl IntoIterator<Item = Edge<C>>) -> Self {
+        let mut edges: Vec<Edge<C>> = edges.into_iter().collect();
+        edges.sort_by_key(|edge| edge.cost);
+        Self { edges: edges.into_iter(), sets: DisjointSets::new(num_nodes) }
+    }
+}
+
+impl<C> Kruskal<C> {
+    /// Components made by the merges so far.
+    pub fn sets(&mut self) -> &mut DisjointSets {
+        &mut self.sets
+    }
+}
+
+impl<C> Iterator for Kruskal<C> {
+    type Item = Merge<C>;
+
+    fn next(&mut self) -> Option<Merge<C>> {
+        for edge in self.edges.by_ref() {
+            let sizes = (self.sets.size(edge.u), self.sets.size(edge.v));
+            if self.sets.join(edge.u, edge.v) {
+                return Some(Merge { edge, sizes, components: self.sets.count() });
+            }
+        }
+        None
+    }
+}
+
+/// Edges of the minimum spanning forest, by Kruskal's algorithm.
+pub fn kruskal<C: Ord + Copy>(num_nodes: usize, edges: impl IntoIterator<Item = Edge<C>>) -> Vec<Edge<C>> {
+    Kruskal::new(num_nodes, edges).map(|merge| merge.edge).collect()
+}
+
+/// Prim's algorithm for dense graphs, O(n^2) calls of 'cost' and no sorting.
+/// 'cost(u, v)' is None if there is no edge. Grows one tree from node 0, then from the first node not reached yet, etc.
+/// Merges come in the order the trees grow, so the first size is that of the tree, the second one is always 1.
+pub fn prim<C: Ord + Copy>(num_nodes: usize, cost: impl Fn(usize, usize) -> Option<C>) -> Vec<Merge<C>> {
+    let mut in_tree = vec![false; num_nodes];
+    // Cheapest known edge from the tree to every node outside of it.
+    let mut best: Vec<Option<Edge<C>>> = vec![None; num_nodes];
+    let mut merges = Vec::new();
+    let mut components = num_nodes;
+    let mut tree_size = 0;
+
+    for _ in 0..num_nodes {
+        let next = (0..num_nodes).filter(|&v| !in_tree[v]).min_by_key(|&v| match best[v] {
+            Some(edge) => (0, Some(edge.cost)),
+            None => (1, None),
+        });
+        let Some(next) = next else { break; };
+        match best[next] {
+            Some(edge) => {
+                components -= 1;
+                merges.push(Merge { edge, sizes: (tree_size, 1), components });
+                tree_size += 1;
+            },
+            None => tree_size = 1, // A new tree.
+        }
+        in_tree[next] = true;
+
+        for v in 0..num_nodes {
+            if in_tree[v] {
+                continue;
+            }
+            if let Some(c) = cost(next, v) {
+                if best[v].is_none_or(|edge| c < edge.cost) {
+                    best[v] = Some(Edge { u: next, v, cost: c });
+                }
+            }
+        }
+    }
+    merges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Nodes 0..5 connected, 5 and 6 apart from them.
+    fn edges() -> Vec<Edge<u32>> {
+        [(0, 4, 5), (0, 1, 1), (3, 4, 4), (2, 3, 2), (5, 6, 7), (1, 2, 3), (1, 3, 6)]
+            .map(|(u, v, cost)| Edge { u, v, cost })
+            .to_vec()
+    }
+
+    fn cost(u: usize, v: usize) -> Option<u32> {
+        edges().into_iter().find(|edge| (edge.u, edge.v) == (u, v) || (edge.v, edge.u) == (u, v)).map(|edge| edge.cost)
+    }
+
+    #[test]
+    fn kruskal_forest() {
+        let tree: Vec<(usize, usize, u32)> = kruskal(7, edges()).iter().map(|edge| (edge.u, edge.v, edge.cost)).collect();
+        assert_eq!(tree, vec![(0, 1, 1), (2, 3, 2), (1, 2, 3), (3, 4, 4), (5, 6, 7)]);
+    }
+
+    #[test]
+    fn merges() {
+        let mut merges = Kruskal::new(7, edges());
+        let stream: Vec<((usize, usize), usize)> = merges.by_ref().map(|merge| (merge.sizes, merge.components)).collect();
+        assert_eq!(stream, vec![((1, 1), 6), ((1, 1), 5), ((2, 2), 4), ((4, 1), 3), ((1, 1), 2)]);
+        assert_eq!(merges.sets().set_sizes(), vec![5, 2]);
+
+        // The merge that connects 0..5, before the edge of 5 and 6 comes.
+        let mut merges = Kruskal::new(5, edges().into_iter().filter(|edge| edge.v < 5));
+        assert_eq!(merges.nth(1).map(|merge| merge.edge.cost), Some(2));
+        assert_eq!(merges.last().map(|merge| (merge.edge.cost, merge.components)), Some((4, 1)));
+    }
+
+    #[test]
+    fn kruskal_and_prim_agree() {
+        let total: u32 = kruskal(7, edges()).iter().map(|edge| edge.cost).sum();
+        assert_eq!(total, 17);
+        let merges = prim(7, cost);
+        assert_eq!(merges.iter().map(|merge| merge.edge.cost).sum::<u32>(), total);
+        assert_eq!(merges.len(), 5);
+        assert_eq!(merges.last().map(|merge| merge.components), Some(2));
+    }
+}
@@ -0,0 +1,161 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use serde::Serialize;
+use crate::runner::{parse_input, read_input, solve_part, Day, Year};
+use crate::Result;
+
+/// Measurements of all the days of one run.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub input: String,
+    /// "release" or "debug". Debug timings are not worth comparing.
+    pub profile: &'static str,
+    /// Seconds since the Unix epoch, when the run started.
+    pub unix_time: u64,
+    pub days: Vec<DayTiming>,
+}
+
+/// Time of parsing and of each part of a single day.
+/// Reading the input file is not measured.
+#[derive(Debug, Serialize)]
+pub struct DayTiming {
+    pub day: u32,
+    pub parse_ms: Option<f64>,
+    pub parts: Vec<PartTiming>,
+    /// Set if the input could not be read or parsed. Parts are not run then.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PartTiming {
+    pub part: u32,
+    pub ms: f64,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+}
+
+impl DayTiming {
+    /// Parse time plus time of all the parts.
+    pub fn total_ms(&self) -> f64 {
+        self.parse_ms.unwrap_or(0.0) + self.parts.iter().map(|p| p.ms).sum::<f64>()
+    }
+}
+
+impl Report {
+    /// Number of days that failed to parse plus number of parts that failed.
+    pub fn failed(&self) -> usize {
+        self.days.iter()
+            .map(|d| if d.error.is_some() { 1 } else { d.parts.iter().filter(|p| p.error.is_some()).count() })
+            .sum()
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+fn time_day(year: &Year, day: &Day, input: &str) -> DayTiming {
+    let mut timing = DayTiming { day: day.day, parse_ms: None, parts: Vec::new(), error: None };
+    let lines = match read_input(year, day, input) {
+        Ok(lines) => lines,
+        Err(err) => {
+            timing.error = Some(err.to_string());
+            return timing;
+        },
+    };
+
+    let start = Instant::now();
+    let parsed = parse_input(day, &lines, input);
+    timing.parse_ms = Some(millis(start.elapsed()));
+    let parsed = match parsed {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            timing.error = Some(err);
+            return timing;
+        },
+    };
+
+    for part in 1..=day.solver.parts() {
+        let start = Instant::now();
+        let result = solve_part(day, parsed.as_ref(), part);
+        let ms = millis(start.elapsed());
+        let (answer, error) = match result {
+            Ok(answer) => (Some(answer), None),
+            Err(err) => (None, Some(err)),
+        };
+        timing.parts.push(PartTiming { part, ms, answer, error });
+    }
+    timing
+}
+
+/// Parses and solves every part of given days, measuring each step separately.
+/// Progress is reported on stderr, since some days take a while.
+pub fn measure(year: &Year, days: &[&Day], input: &str) -> Report {
+    let unix_time = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let profile = if cfg!(debug_assertions) { "debug" } else { "release" };
+    let mut timings = Vec::new();
+    for day in days {
+        eprintln!("Timing day {}...", day.day);
+        timings.push(time_day(year, day, input));
+    }
+    Report { input: input.to_string(), profile, unix_time, days: timings }
+}
+
+fn format_ms(ms: Option<f64>) -> String {
+    match ms {
+        Some(ms) => format!("{:.3} ms", ms),
+        None => "-".to_string(),
+    }
+}
+
+/// Prints one row per day and a total. Failed steps are marked and their errors listed below the table.
+pub fn print_table(report: &Report) {
+    if report.profile == "debug" {
+        println!("Note: debug build, run with --release for meaningful timings.");
+    }
+    println!("{:>4}  {:>14}  {:>14}  {:>14}  {:>14}", "Day", "Parse", "Part 1", "Part 2", "Total");
+
+    let mut errors = Vec::new();
+    let mut total = 0.0;
+    for day in &report.days {
+        let mut cells = vec![format_ms(day.parse_ms)];
+        for part in 1..=2 {
+            let cell = match day.parts.iter().find(|p| p.part == part) {
+                Some(p) if p.error.is_some() => format!("FAILED {}", format_ms(Some(p.ms))),
+                Some(p) => format_ms(Some(p.ms)),
+                None if day.error.is_some() => "FAILED".to_string(),
+                None => "-".to_string(),
+            };
+            cells.push(cell);
+        }
+        println!("{:>4}  {:>14}  {:>14}  {:>14}  {:>14}", day.day, cells[0], cells[1], cells[2], format_ms(Some(day.total_ms())));
+        total += day.total_ms();
+
+        if let Some(err) = &day.error {
+            errors.push(format!("Day {}: {}", day.day, err));
+        }
+        for part in &day.parts {
+            if let Some(err) = &part.error {
+                errors.push(format!("Day {} part {}: {}", day.day, part.part, err));
+            }
+        }
+    }
+    println!("{:>4}  {:>14}  {:>14}  {:>14}  {:>14}", "All", "", "", "", format_ms(Some(total)));
+
+    for err in errors {
+        eprintln!("{}", err);
+    }
+}
+
+/// Writes the report as JSON. Path '-' means stdout.
+pub fn write_json(report: &Report, path: &Path) -> Result<()> {
+    if path == Path::new("-") {
+        serde_json::to_writer_pretty(io::stdout(), report)?;
+        println!();
+    } else {
+        fs::write(path, serde_json::to_string_pretty(report)?)?;
+    }
+    Ok(())
+}
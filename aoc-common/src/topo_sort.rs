@@ -0,0 +1,208 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::hash::Hash;
+use crate::interner::Interner;
+
+/// The order is not acyclic. 'cycle' goes along the edges and its last node leads back to the first one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<T> {
+    pub cycle: Vec<T>,
+}
+
+impl<T> CycleError<T> {
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> CycleError<U> {
+        CycleError { cycle: self.cycle.into_iter().map(f).collect() }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Display for CycleError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cycle ")?;
+        for node in &self.cycle {
+            write!(f, "{:?} -> ", node)?;
+        }
+        match self.cycle.first() {
+            Some(first) => write!(f, "{:?}", first),
+            None => write!(f, "(empty)"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> Error for CycleError<T> {}
+
+/// Kahn's algorithm on dense ids 0 .. successors.len(). Of the nodes that are ready at the same time,
+/// the one with the smallest 'key' goes first, so the result does not depend on hashing or insertion order.
+pub(crate) fn kahn<K: Ord>(successors: &[Vec<usize>], key: impl Fn(usize) -> K) -> Result<Vec<usize>, CycleError<usize>> {
+    let mut in_degrees = vec![0; successors.len()];
+    for &v in successors.iter().flatten() {
+        in_degrees[v] += 1;
+    }
+
+    let mut ready: BinaryHeap<_> = (0..successors.len()).filter(|&u| in_degrees[u] == 0).map(|u| Reverse((key(u), u))).collect();
+    let mut order = Vec::with_capacity(successors.len());
+    while let Some(Reverse((_, u))) = ready.pop() {
+        order.push(u);
+        for &v in &successors[u] {
+            in_degrees[v] -= 1;
+            if in_degrees[v] == 0 {
+                ready.push(Reverse((key(v), v)));
+            }
+        }
+    }
+
+    if order.len() == successors.len() {
+        return Ok(order);
+    }
+    Err(find_cycle(successors, &in_degrees, key))
+}
+
+/// Every node Kahn's algorithm could not output has a predecessor it could not output either.
+/// Following those predecessors must therefore run in a circle.
+fn find_cycle<K: Ord>(successors: &[Vec<usize>], in_degrees: &[usize], key: impl Fn(usize) -> K) -> CycleError<usize> {
+    let mut predecessor = vec![None; successors.len()];
+    for (u, next) in successors.iter().enumerate() {
+        if in_degrees[u] > 0 {
+            for &v in next {
+                predecessor[v] = Some(u);
+            }
+        }
+    }
+
+    let mut seen = vec![false; successors.len()];
+    let mut path = Vec::new();
+    let mut u = (0..successors.len()).filter(|&u| in_degrees[u] > 0).min_by_key(|&u| key(u)).unwrap();
+    while !seen[u] {
+        seen[u] = true;
+        path.push(u);
+        u = predecessor[u].unwrap();
+    }
+    let from = path.iter().position(|&v| v == u).unwrap();
+    let mut cycle = path.split_off(from);
+    cycle.reverse();
+    // Start the cycle at its smallest node.
+    let first = (0..cycle.len()).min_by_key(|&idx| key(cycle[idx])).unwrap();
+    cycle.rotate_left(first);
+    CycleError { cycle }
+}
+
+/// Set of "'before' must come before 'after'" constraints, which can order all of its nodes or any subset of them.
+/// Ties are broken by the order of T.
+#[derive(Debug, Clone)]
+pub struct Precedence<T: Clone + Eq + Hash + Ord> {
+    nodes: Interner<T>,
+    successors: Vec<Vec<usize>>,
+}
+
+impl<T: Clone + Eq + Hash + Ord> Precedence<T> {
+    /// Pairs (before, after).
+    pub fn new(edges: impl IntoIterator<Item = (T, T)>) -> Self {
+        let mut precedence = Self { nodes: Interner::new(), successors: Vec::new() };
+        for (before, after) in edges {
+            precedence.add_edge(before, after);
+        }
+        precedence
+    }
+
+    /// A node with no constraints, so that 'sort' includes it.
+    pub fn add_node(&mut self, u: T) -> usize {
+        let id = self.nodes.intern(u);
+        if id == self.successors.len() {
+            self.successors.push(Vec::new());
+        }
+        id
+    }
+
+    pub fn add_edge(&mut self, before: T, after: T) {
+        let before = self.add_node(before);
+        let after = self.add_node(after);
+        self.successors[before].push(after);
+    }
+
+    /// All the nodes, each after all of its predecessors.
+    pub fn sort(&self) -> Result<Vec<T>, CycleError<T>> {
+        let order = kahn(&self.successors, |id| self.nodes.value(id)).map_err(|err| err.map(|id| self.nodes.value(id).clone()))?;
+        Ok(order.into_iter().map(|id| self.nodes.value(id).clone()).collect())
+    }
+
+    /// Orders just the 'subset', only the constraints between its members count.
+    /// The constraints as a whole may have cycles, as long as the subset does not.
+    pub fn sort_subset(&self, subset: &[T]) -> Result<Vec<T>, CycleError<T>> {
+        let local: HashMap<usize, usize> = subset.iter().enumerate()
+            .filter_map(|(idx, u)| self.nodes.get(u).map(|id| (id, idx)))
+            .collect();
+        let successors: Vec<Vec<usize>> = subset.iter()
+            .map(|u| match self.nodes.get(u) {
+                Some(id) => self.successors[id].iter().filter_map(|v| local.get(v).copied()).collect(),
+                None => Vec::new(),
+            })
+            .collect();
+        let order = kahn(&successors, |idx| &subset[idx]).map_err(|err| err.map(|idx| subset[idx].clone()))?;
+        Ok(order.into_iter().map(|idx| subset[idx].clone()).collect())
+    }
+
+    /// True if no constraint between members of 'sequence' is violated.
+    pub fn is_sorted(&self, sequence: &[T]) -> bool {
+        let positions: HashMap<usize, usize> = sequence.iter().enumerate()
+            .filter_map(|(idx, u)| self.nodes.get(u).map(|id| (id, idx)))
+            .collect();
+        positions.iter().all(|(&id, &pos)| {
+            self.successors[id].iter().all(|v| positions.get(v).is_none_or(|&next_pos| next_pos > pos))
+        })
+    }
+}
+
+/// Topological sort of 'nodes' and all the nodes of 'edges'. Edge (x, y) means that x will be first in the result.
+/// Ties are broken by the order of T.
+pub fn topo_sort<T: Clone + Eq + Hash + Ord>(nodes: impl IntoIterator<Item = T>, edges: impl IntoIterator<Item = (T, T)>) -> Result<Vec<T>, CycleError<T>> {
+    let mut precedence = Precedence::new(edges);
+    for u in nodes {
+        precedence.add_node(u);
+    }
+    precedence.sort()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(edges: &[&str]) -> Vec<(char, char)> {
+        edges.iter().map(|edge| (edge.as_bytes()[0] as char, edge.as_bytes()[1] as char)).collect()
+    }
+
+    #[test]
+    fn ties() {
+        let mut precedence = Precedence::new(chars(&["db", "cb"]));
+        precedence.add_node('a');
+        assert_eq!(precedence.sort(), Ok(vec!['a', 'c', 'd', 'b']));
+        assert_eq!(topo_sort(['z'], chars(&["ba"])), Ok(vec!['b', 'a', 'z']));
+    }
+
+    #[test]
+    fn subset_outside_cycle() {
+        let precedence = Precedence::new(chars(&["ab", "bc", "ca", "ad"]));
+        assert!(precedence.sort().is_err());
+        assert_eq!(precedence.sort_subset(&['d', 'b', 'a']), Ok(vec!['a', 'b', 'd']));
+        assert_eq!(precedence.sort_subset(&['x', 'd']), Ok(vec!['d', 'x']));
+    }
+
+    #[test]
+    fn sorted() {
+        let precedence = Precedence::new(chars(&["ab", "bc"]));
+        assert!(precedence.is_sorted(&['a', 'b', 'c']));
+        assert!(precedence.is_sorted(&['a', 'x', 'c']));
+        assert!(precedence.is_sorted(&['c', 'a']));
+        assert!(!precedence.is_sorted(&['b', 'a']));
+        assert!(!precedence.is_sorted(&['a', 'c', 'b']));
+    }
+
+    #[test]
+    fn cycle() {
+        let precedence = Precedence::new(chars(&["cd", "ab", "db", "bc"]));
+        let err = precedence.sort().unwrap_err();
+        assert_eq!(err.cycle, vec!['b', 'c', 'd']);
+        assert_eq!(err.to_string(), "cycle 'b' -> 'c' -> 'd' -> 'b'");
+        assert_eq!(precedence.sort_subset(&['d', 'c', 'b']).unwrap_err().cycle, vec!['b', 'c', 'd']);
+    }
+}
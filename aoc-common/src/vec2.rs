@@ -1,21 +1,18 @@
 use std::ops;
 use num::Signed;
 
-#[allow(dead_code)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
-pub(crate) struct Vec2<T = i32> {
+pub struct Vec2<T = i32> {
     pub x: T,
     pub y: T,
 }
 
-#[allow(dead_code)]
 impl<T> Vec2<T> {
     pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 }
 
-#[allow(dead_code)]
 impl<T: Signed + Copy> Vec2<T> {
     pub fn rot_cw(&self) -> Self {
         Self::new(-self.y, self.x)
@@ -23,6 +20,9 @@ impl<T: Signed + Copy> Vec2<T> {
     pub fn rot_ccw(&self) -> Self {
         Self::new(self.y, -self.x)
     }
+    pub fn manhattan(&self) -> T {
+        self.x.abs() + self.y.abs()
+    }
 }
 
 /*
@@ -36,7 +36,6 @@ const fn literal_hacky<T: Pod>(val: i8) -> T
 }
 */
 
-#[allow(dead_code)]
 impl<T: Signed> Vec2<T> {
     pub fn zero() -> Self { Self { x: T::zero(), y: T::zero() } }
     pub fn up() -> Self { Self { x: T::zero(), y: -T::one() } }
@@ -0,0 +1,4 @@
+/// Generates the answers.txt regression tests, see aoc_common::answers.
+fn main() {
+    aoc_common::answers::generate_tests();
+}
@@ -1,34 +1,33 @@
-use regex::Regex;
-use crate::basic_parsing::read_lines;
-
-#[derive(Debug, Clone, Copy)]
-struct Input {
-    left: bool,
-    num: i64,
+use aoc_common::basic_parsing::parse_lines;
+use aoc_common::line_struct;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
+
+line_struct! {
+    "{dir:[LR]}{num}"
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct Input {
+        dir: char,
+        num: i64,
+    }
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
-    let mut inputs: Vec<Input> = Vec::new();
-
-    let in_regex = Regex::new(r"^(?<dir>[LR])(?<num>\d+)$").unwrap();
-    for (idx, line) in lines.iter().enumerate() {
-        let cap = in_regex.captures(line).ok_or(format!("Line idx={} (zero-based) '{}' does not match the regex.", idx, line))?;
-
-        let input = Input {
-            left: &cap["dir"] == "L",
-            num: cap["num"].parse::<i64>().unwrap(),
-        };
-        inputs.push(input);
+impl Input {
+    fn left(&self) -> bool {
+        self.dir == 'L'
     }
+}
 
-    Ok(inputs)
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Input>> {
+    parse_lines(lines)
 }
 
 fn compute_stuff(inputs: &Vec<Input>, start_pos: i64) -> i64 {
     let mut current_pos = start_pos;
     let mut count: i64 = if start_pos == 0 { 1 } else { 0 };
     for input in inputs {
-        current_pos += if input.left { -input.num } else { input.num };
+        current_pos += if input.left() { -input.num } else { input.num };
         current_pos %= 100;
         if current_pos < 0 {
             current_pos += 100;
@@ -51,7 +50,7 @@ fn compute_stuff2(inputs: &Vec<Input>, start_pos: i64) -> i64 {
 
         let pre_pos = current_pos;
 
-        if input.left {
+        if input.left() {
             current_pos = -current_pos;
         }
 
@@ -62,7 +61,7 @@ fn compute_stuff2(inputs: &Vec<Input>, start_pos: i64) -> i64 {
         current_pos %= 100;
         if current_pos < 0 { panic!("Impossible") }
 
-        if input.left {
+        if input.left() {
             current_pos = (100 - current_pos) % 100;
         }
         if current_pos < 0 { panic!("Impossible") }
@@ -78,30 +77,32 @@ fn compute_stuff2(inputs: &Vec<Input>, start_pos: i64) -> i64 {
             continue;
         }
 
-        if input.left && (current_pos > pre_pos) {
+        if input.left() && (current_pos > pre_pos) {
             count += 1;
         }
-        if !input.left && (current_pos < pre_pos) {
+        if !input.left() && (current_pos < pre_pos) {
             count += 1;
         }
     }
     return count; // 6583 too low
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec1() {
-    let lines = read_lines("dec1.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = compute_stuff(&inputs, 50);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec1;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec1_2() {
-    let lines = read_lines("dec1.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = compute_stuff2(&inputs, 50);
-    println!("{:?}", result);
+impl Solver for Dec1 {
+    type Input = Vec<Input>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Input>> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, inputs: &Vec<Input>) -> Result<i64> {
+        Ok(compute_stuff(inputs, 50))
+    }
+
+    fn part2(&self, inputs: &Vec<Input>) -> Result<i64> {
+        Ok(compute_stuff2(inputs, 50))
+    }
 }
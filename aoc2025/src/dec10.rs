@@ -3,7 +3,10 @@ use itertools::Itertools;
 use nom::character::char;
 use nom::combinator::all_consuming;
 use nom::sequence::delimited;
-use crate::basic_parsing::read_lines;
+use aoc_common::parse_error::{ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::search::bfs;
+use aoc_common::solver::Solver;
 use nom::{IResult, Parser};
 use nom::{bytes::complete::tag, multi::separated_list1};
 use nom::branch::alt;
@@ -11,7 +14,7 @@ use nom::character::complete::i64 as cc_i64;
 use nom::multi::many1;
 
 #[derive(Debug, Clone)]
-struct Input {
+pub(crate) struct Input {
     leds: Vec::<bool>,
     buttons: Vec<Vec<i64>>,
     joltages: Vec<i64>,
@@ -43,11 +46,12 @@ fn parse_machine(input: &str) -> IResult<&str, Input> {
         }))
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Input>> {
     let mut inputs: Vec<Input> = Vec::new();
 
-    for (_idx, line) in lines.iter().enumerate() {
-        let (_, input) = parse_machine(line.as_str()).unwrap();
+    for (idx, line) in lines.iter().enumerate() {
+        let (_, input) = parse_machine(line.as_str())
+            .map_err(|err| ParseError::from_nom(idx, line, err, "machine like [.##.] (3) (1,3) {3,5}"))?;
         inputs.push(input);
     }
 
@@ -56,33 +60,16 @@ fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
 
 fn solve_machine(machine: &Input) -> i64 {
     let start_leds: Vec<bool> = vec![false; machine.leds.len()];
-    let mut queue: VecDeque<(i64, Vec<bool>)> = VecDeque::new();
-    let mut visited: HashSet<Vec<bool>> = HashSet::new(); // All nodes that were put into queue at any point.
-    queue.push_back((0, start_leds.clone()));
-    visited.insert(start_leds);
-    loop {
-        if let Some((rank, leds)) = queue.pop_front() {
-            if leds == machine.leds {
-                return rank;
-            }
-
-            for button in &machine.buttons {
-                let mut new_leds = leds.clone();
-                for &led in button {
-                    new_leds[led as usize] = !new_leds[led as usize];
-                }
-
-                if visited.contains(&new_leds) {
-                    continue;
-                }
-
-                queue.push_back((rank + 1, new_leds.clone()));
-                visited.insert(new_leds);
-            }
-        } else {
-            panic!("Did not find any solution.")
+    let press_buttons = |leds: &Vec<bool>| machine.buttons.iter().map(|button| {
+        let mut new_leds = leds.clone();
+        for &led in button {
+            new_leds[led as usize] = !new_leds[led as usize];
         }
-    }
+        new_leds
+    }).collect_vec();
+
+    let result = bfs([start_leds], press_buttons, |leds| *leds == machine.leds);
+    result.goal_dist().expect("Did not find any solution.") as i64
 }
 
 #[allow(dead_code)]
@@ -166,13 +153,6 @@ fn solve_machine3(machine: &Input) -> i64 {
         }
     }
 
-    for (counter_idx, buttons) in counter_to_buttons.iter().enumerate() {
-        for &button_idx in buttons {
-            print!("b{} + ", button_idx)
-        }
-        println!("0 = {}", machine.joltages[counter_idx]);
-    }
-
     0
 }
 
@@ -181,26 +161,30 @@ fn solve_task2(inputs: &Vec<Input>) -> i64 {
     let mut result = 0;
     for input in inputs {
         let res = solve_machine3(input);
-        println!("Machine: {}", res);
         result += res;
     }
     return result;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec10() {
-    let lines = read_lines("dec10.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task(&inputs);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec10;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec10_2() {
-    let lines = read_lines("dec10.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task2(&inputs);
-    println!("{:?}", result);
+/// Only part 1 is solved.
+impl Solver for Dec10 {
+    type Input = Vec<Input>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    const PARTS: u32 = 1;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Input>> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, inputs: &Vec<Input>) -> Result<i64> {
+        Ok(solve_task(inputs))
+    }
+
+    fn part2(&self, _inputs: &Vec<Input>) -> Result<i64> {
+        Err("Part 2 is not solved.".into())
+    }
 }
@@ -1,27 +1,28 @@
 use std::collections::HashSet;
 use regex::Regex;
-use crate::basic_parsing::read_lines;
+use aoc_common::parse_error::{parse_token, ParseError, ParseResult};
+use aoc_common::basic_parsing::single_line;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 #[derive(Debug, Clone, Copy)]
-struct Input {
+pub(crate) struct Input {
     start: i64, // Inclusive.
     end: i64,   // Inclusive.
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Input>> {
     let mut inputs: Vec<Input> = Vec::new();
-    if lines.len() != 1 {
-        return Err(format!("Expected exactly one line of input, but got {}.", lines.len()).into());
-    }
-    let pieces = &lines[0].split(",").collect::<Vec<&str>>();
+    let line = single_line(lines)?;
+    let pieces = &line.split(",").collect::<Vec<&str>>();
 
     let in_regex = Regex::new(r"^(?<start>\d+)-(?<end>\d+)$").unwrap();
-    for (idx, piece) in pieces.iter().enumerate() {
-        let cap = in_regex.captures(piece).ok_or(format!("Piece idx={} (zero-based) '{}' does not match the regex.", idx, piece))?;
+    for piece in pieces.iter() {
+        let cap = in_regex.captures(piece).ok_or(ParseError::at(0, line, piece, "range like 11-22"))?;
 
         let input = Input {
-            start: cap["start"].parse::<i64>().unwrap(),
-            end: cap["end"].parse::<i64>().unwrap(),
+            start: parse_token(0, line, &cap["start"])?,
+            end: parse_token(0, line, &cap["end"])?,
         };
         inputs.push(input);
     }
@@ -31,9 +32,6 @@ fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
 
 #[allow(dead_code)]
 fn find_invalids(mut input: Input) -> i64 {
-    println!("============================================");
-    println!("start={} end={}", input.start, input.end);
-
     // Find lowest number >= start, with even number of digits.
     let mut start_digits = input.start.ilog10() + 1;
     if (start_digits & 1) != 0 {
@@ -47,8 +45,6 @@ fn find_invalids(mut input: Input) -> i64 {
         input.end = 10i64.pow(end_digits) - 1;
     }
 
-    println!("start={} end={}", input.start, input.end);
-    println!("start_digits={} end_digits={}", start_digits, end_digits);
     if end_digits == 0 {
         return 0;
     }
@@ -63,26 +59,19 @@ fn find_invalids(mut input: Input) -> i64 {
     let end_high = input.end / end_half_power;
     let end_low = input.end % end_half_power;
 
-    println!("start_half_power={} start_high={} start_low={}", start_half_power, start_high, start_low);
-    println!("end_half_power={} end_high={} end_low={}", end_half_power, end_high, end_low);
-
     if end_high < start_high {
-        println!("result={}", 0);
         return 0;
     }
 
     let start_ok = if start_high < start_low { start_high + 1 } else { start_high };
     let end_ok = if end_high > end_low { end_high - 1 } else { end_high };
 
-    let mut result = 0;
     let mut real_result = 0;
     for i in start_ok..=end_ok {
-        result += i;
         let i_digits = i.ilog10() + 1;
         real_result += i * 10i64.pow(i_digits) + i;
     }
 
-    println!("result={} real_result={}", result, real_result);
     return real_result;
 }
 
@@ -109,9 +98,6 @@ fn decompose_num(mut num: i64, power: i64) -> Vec<i64> {
 }
 
 fn find_invalids2(mut input: Input, num_parts: u32, found_invalids: &mut HashSet<i64>) -> i64 {
-    println!("============================================");
-    println!("num_parts={} start={} end={}", num_parts, input.start, input.end);
-
     // Find lowest number >= start, with number of digits divisible by num_parts.
     let mut start_digits = input.start.ilog10() + 1;
     if (start_digits % num_parts) != 0 {
@@ -126,8 +112,6 @@ fn find_invalids2(mut input: Input, num_parts: u32, found_invalids: &mut HashSet
         input.end = 10i64.pow(end_digits) - 1;
     }
 
-    println!("start={} end={}", input.start, input.end);
-    println!("start_digits={} end_digits={}", start_digits, end_digits);
     if end_digits == 0 {
         return 0;
     }
@@ -140,9 +124,6 @@ fn find_invalids2(mut input: Input, num_parts: u32, found_invalids: &mut HashSet
     let end_block_power = 10i64.pow(end_digits / num_parts);
     let end_pieces = decompose_num(input.end, end_block_power);
 
-    println!("start_block_power={} start_pieces={:?}", start_block_power, start_pieces);
-    println!("end_block_power={} end_pieces={:?}", end_block_power, end_pieces);
-
     let start_high = *start_pieces.last().unwrap();
     let mut start_ok = start_high;
     for &piece in start_pieces.iter().rev() {
@@ -167,12 +148,8 @@ fn find_invalids2(mut input: Input, num_parts: u32, found_invalids: &mut HashSet
         }
     }
 
-    println!("start_ok={} end_ok={}", start_ok, end_ok);
-
-    let mut result = 0;
     let mut real_result = 0;
     for i in start_ok..=end_ok {
-        result += i;
         let i_digits = i.ilog10() + 1;
         let mut part_result = 0;
         for _k in 1..=num_parts {
@@ -180,7 +157,6 @@ fn find_invalids2(mut input: Input, num_parts: u32, found_invalids: &mut HashSet
         }
 
         if found_invalids.contains(&part_result) {
-            println!("Skipping duplicate part_result={}", part_result);
             continue;
         }
         found_invalids.insert(part_result);
@@ -188,7 +164,6 @@ fn find_invalids2(mut input: Input, num_parts: u32, found_invalids: &mut HashSet
         real_result += part_result;
     }
 
-    println!("result={} real_result={}", result, real_result);
     return real_result;
 }
 
@@ -205,20 +180,22 @@ fn solve_task2(inputs: &Vec<Input>) -> i64 {
     return result;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec2() {
-    let lines = read_lines("dec2.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task(&inputs);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec2;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec2_2() {
-    let lines = read_lines("dec2.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task2(&inputs);
-    println!("{:?}", result);
+impl Solver for Dec2 {
+    type Input = Vec<Input>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Input>> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, inputs: &Vec<Input>) -> Result<i64> {
+        Ok(solve_task(inputs))
+    }
+
+    fn part2(&self, inputs: &Vec<Input>) -> Result<i64> {
+        Ok(solve_task2(inputs))
+    }
 }
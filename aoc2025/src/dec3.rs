@@ -1,5 +1,7 @@
-use crate::basic_parsing::read_lines;
-use crate::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::solver::Solver;
 
 #[allow(dead_code)]
 fn bank_voltage(bank: &Vec<i32>) -> i32 {
@@ -48,20 +50,22 @@ fn solve_task2(matrix: &Matrix) -> i64 {
     return result;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec3() {
-    let lines = read_lines("dec3.in.txt").expect("Could not load input.");
-    let matrix = read_matrix_from_lines(lines).unwrap();
-    let result = solve_task(&matrix);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec3;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec3_2() {
-    let lines = read_lines("dec3.in.txt").expect("Could not load input.");
-    let matrix = read_matrix_from_lines(lines).unwrap();
-    let result = solve_task2(&matrix);
-    println!("{:?}", result);
+impl Solver for Dec3 {
+    type Input = Matrix;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Matrix> {
+        read_matrix_from_lines(lines.to_vec())
+    }
+
+    fn part1(&self, matrix: &Matrix) -> Result<i64> {
+        Ok(solve_task(matrix))
+    }
+
+    fn part2(&self, matrix: &Matrix) -> Result<i64> {
+        Ok(solve_task2(matrix))
+    }
 }
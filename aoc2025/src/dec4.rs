@@ -1,33 +1,21 @@
 use std::collections::HashSet;
-use crate::basic_parsing::read_lines;
-use crate::matrix::{find_points, read_matrix_from_lines, Matrix};
-use crate::vec2::Vec2;
+use aoc_common::grid::MOORE;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::matrix::{find_points, read_matrix_from_lines, Matrix};
+use aoc_common::solver::Solver;
+use aoc_common::vec2::Vec2;
 
 #[allow(dead_code)]
 fn solve_task(matrix: &Matrix) -> i64 {
     let mut accessible_rolls: i64 = 0;
-    for y in 0..matrix.height {
-        for x in 0..matrix.width {
-            let pos = Vec2::new(x as i32, y as i32);
-
-            if matrix.get(pos).unwrap() != '@' {
-                continue;
-            }
-
-            let mut neighbour_rolls = 0;
-            for dy in -1..=1 {
-                for dx in -1..=1 {
-                    if dy == 0 && dx == 0 { continue; }
-                    let dpos = pos + Vec2::new(dx, dy);
-                    if matrix.get(dpos).unwrap_or('.') == '@' {
-                        neighbour_rolls += 1;
-                    }
-                }
-            }
+    for pos in matrix.positions() {
+        if matrix.get(pos).unwrap() != '@' {
+            continue;
+        }
 
-            if neighbour_rolls < 4 {
-                accessible_rolls += 1;
-            }
+        if matrix.count_neighbors(pos, &MOORE, |c| c == '@') < 4 {
+            accessible_rolls += 1;
         }
     }
     return accessible_rolls;
@@ -57,19 +45,9 @@ fn solve_task2(mut matrix: &mut Matrix) -> i64 {
             }
 
             // Check if roll can be accessed by a forklift.
-            let mut neighbour_rolls = 0;
-            for dy in -1..=1 {
-                for dx in -1..=1 {
-                    if dy == 0 && dx == 0 { continue; }
-                    let dpos = roll + Vec2::new(dx, dy);
-                    if matrix.get(dpos).unwrap_or('.') == '@' {
-                        neighbour_rolls += 1;
-                        if neighbour_rolls >=4 {
-                            // Current roll is not awaken. Skip to the next one.
-                            continue 'next_roll;
-                        }
-                    }
-                }
+            if matrix.count_neighbors(roll, &MOORE, |c| c == '@') >= 4 {
+                // Current roll is not awaken. Skip to the next one.
+                continue 'next_roll;
             }
 
             // Removing current roll, but waking up neighboring rolls.
@@ -77,13 +55,9 @@ fn solve_task2(mut matrix: &mut Matrix) -> i64 {
             matrix.put(roll, '.');
             removed_rolls_count += 1;
 
-            for dy in -1..=1 {
-                for dx in -1..=1 {
-                    if dy == 0 && dx == 0 { continue; }
-                    let dpos = roll + Vec2::new(dx, dy);
-                    if matrix.get(dpos).unwrap_or('.') == '@' {
-                        new_awake_rolls.insert(dpos);
-                    }
+            for dpos in matrix.neighbors8(roll) {
+                if matrix.get(dpos) == Some('@') {
+                    new_awake_rolls.insert(dpos);
                 }
             }
         }
@@ -94,20 +68,23 @@ fn solve_task2(mut matrix: &mut Matrix) -> i64 {
     return removed_rolls_count;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec4() {
-    let lines = read_lines("dec4.in.txt").expect("Could not load input.");
-    let matrix = read_matrix_from_lines(lines).unwrap();
-    let result = solve_task(&matrix);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec4;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec4_2() {
-    let lines = read_lines("dec4.in.txt").expect("Could not load input.");
-    let mut matrix = read_matrix_from_lines(lines).unwrap();
-    let result = solve_task2(&mut matrix);
-    println!("{:?}", result);
+impl Solver for Dec4 {
+    type Input = Matrix;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Matrix> {
+        read_matrix_from_lines(lines.to_vec())
+    }
+
+    fn part1(&self, matrix: &Matrix) -> Result<i64> {
+        Ok(solve_task(matrix))
+    }
+
+    fn part2(&self, matrix: &Matrix) -> Result<i64> {
+        let mut matrix = matrix.clone();
+        Ok(solve_task2(&mut matrix))
+    }
 }
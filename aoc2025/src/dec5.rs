@@ -1,12 +1,18 @@
 use std::cmp::max;
 use itertools::Itertools;
-use regex::Regex;
-use crate::basic_parsing::{read_lines, separate_by_blank};
+use aoc_common::basic_parsing::{parse_lines, separate_by_blank};
+use aoc_common::line_struct;
+use aoc_common::parse_error::{parse_token, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
-#[derive(Debug, Clone, Copy)]
-struct InclusiveRange {
-    start: i64, // Inclusive.
-    end: i64,   // Inclusive.
+line_struct! {
+    "{start}-{end}"
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct InclusiveRange {
+        start: i64, // Inclusive.
+        end: i64,   // Inclusive.
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -35,21 +41,8 @@ impl From<InclusiveRange> for ExclusiveRange {
     }
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<InclusiveRange>> {
-    let mut inputs: Vec<InclusiveRange> = Vec::new();
-
-    let in_regex = Regex::new(r"^(?<start>\d+)-(?<end>\d+)$").unwrap();
-    for (idx, line) in lines.iter().enumerate() {
-        let cap = in_regex.captures(line).ok_or(format!("Line idx={} (zero-based) '{}' does not match the regex.", idx, line))?;
-
-        let input = InclusiveRange {
-            start: cap["start"].parse::<i64>().unwrap(),
-            end: cap["end"].parse::<i64>().unwrap(),
-        };
-        inputs.push(input);
-    }
-
-    Ok(inputs)
+fn parse_input(lines: &[String]) -> ParseResult<Vec<InclusiveRange>> {
+    parse_lines(lines)
 }
 
 #[allow(dead_code)]
@@ -92,24 +85,30 @@ fn solve_task2(ranges: &Vec<ExclusiveRange>) -> i64 {
     return result;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec5() {
-    let lines = read_lines("dec5.in.txt").expect("Could not load input.");
-    let (range_lines, id_lines) = separate_by_blank(&lines);
-    let ranges = parse_input(&range_lines).unwrap();
-    let ids = id_lines.iter().map(|id| id.parse::<i64>().unwrap()).collect();
-    let result = solve_task(&ranges, &ids);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec5;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec5_2() {
-    let lines = read_lines("dec5.in.txt").expect("Could not load input.");
-    let (range_lines, id_lines) = separate_by_blank(&lines);
-    let in_ranges = parse_input(&range_lines).unwrap();
-    let ex_ranges = in_ranges.into_iter().map(Into::into).collect();
-    let result = solve_task2(&ex_ranges);
-    println!("{:?}", result);
+impl Solver for Dec5 {
+    type Input = (Vec<InclusiveRange>, Vec<i64>);
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Self::Input> {
+        let (range_lines, id_lines) = separate_by_blank(lines);
+        let ranges = parse_input(&range_lines)?;
+        // Ids follow the ranges and the blank line.
+        let first_id_idx = range_lines.len() + 1;
+        let ids = id_lines.iter().enumerate()
+            .map(|(idx, id)| parse_token::<i64>(first_id_idx + idx, id, id))
+            .collect::<ParseResult<_>>()?;
+        Ok((ranges, ids))
+    }
+
+    fn part1(&self, (ranges, ids): &Self::Input) -> Result<i64> {
+        Ok(solve_task(ranges, ids))
+    }
+
+    fn part2(&self, (ranges, _ids): &Self::Input) -> Result<i64> {
+        let ex_ranges = ranges.iter().copied().map(Into::into).collect();
+        Ok(solve_task2(&ex_ranges))
+    }
 }
@@ -1,6 +1,8 @@
 use itertools::Itertools;
 use regex::Regex;
-use crate::basic_parsing::read_lines;
+use aoc_common::parse_error::{parse_token, ParseError, ParseResult};
+use aoc_common::Result;
+use aoc_common::solver::Solver;
 
 
 #[derive(Debug, Clone)]
@@ -9,16 +11,32 @@ struct Input{
     operator: char,
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
+/// Last line holds the operators ('+' or '*'), one for each column of numbers.
+fn parse_operators(lines: &[String]) -> ParseResult<Vec<char>> {
+    let in_regex = Regex::new(r"\s+").unwrap();
+    let op_idx = lines.len().checked_sub(1).ok_or(ParseError::end_of_input(lines, "line of operators"))?;
+    let operator_line = &lines[op_idx];
+    in_regex.split(operator_line.trim())
+        .map(|s| match s {
+            "+" => Ok('+'),
+            "*" => Ok('*'),
+            _ => Err(ParseError::at(op_idx, operator_line, s, "+ or *")),
+        })
+        .collect()
+}
+
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Input>> {
     let in_regex = Regex::new(r"\s+").unwrap();
 
-    let operator_line = lines.last().unwrap();
-    let ops = in_regex.split(operator_line.trim()).map(|s| s.chars().next().unwrap()).collect_vec();
+    let ops = parse_operators(lines)?;
 
     let number_lines = &lines[0..lines.len() - 1];
     let mut num_lines: Vec<Vec<i64>> = vec![];
-    for (_idx, line) in number_lines.iter().enumerate() {
-        let nums = in_regex.split(line.trim()).map(|s| s.parse::<i64>().unwrap()).collect_vec();
+    for (idx, line) in number_lines.iter().enumerate() {
+        let nums = in_regex.split(line.trim()).map(|s| parse_token::<i64>(idx, line, s)).collect::<ParseResult<Vec<_>>>()?;
+        if nums.len() != ops.len() {
+            return Err(ParseError::new(idx, line, line.chars().count(), format!("{} numbers, one for each operator", ops.len())));
+        }
         num_lines.push(nums);
     }
 
@@ -34,20 +52,22 @@ fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
     Ok(inputs)
 }
 
-fn parse_input2(input_lines: &Vec<String>) -> crate::helpers::Result<Vec<Input>> {
-    let in_regex = Regex::new(r"\s+").unwrap();
-    let operator_line = input_lines.last().unwrap();
-    let ops = in_regex.split(operator_line.trim()).map(|s| s.chars().next().unwrap()).collect_vec();
+fn parse_input2(input_lines: &[String]) -> ParseResult<Vec<Input>> {
+    let ops = parse_operators(input_lines)?;
 
     let number_lines = &input_lines[0..input_lines.len() - 1];
-    let longest_line = number_lines.iter().map(|l| l.len()).max().unwrap();
+    let longest_line = number_lines.iter().map(|l| l.chars().count()).max()
+        .ok_or(ParseError::line(0, &input_lines[0], "lines of numbers above the operators"))?;
     let mut columns = vec![vec![]; longest_line];
 
-    for (_line_idx, line) in number_lines.iter().enumerate() {
+    for (line_idx, line) in number_lines.iter().enumerate() {
         for (char_idx, c) in line.chars().enumerate() {
             if c == ' ' {
                 continue;
             }
+            if !c.is_ascii_digit() {
+                return Err(ParseError::new(line_idx, line, char_idx, "digit or space"));
+            }
             columns[char_idx].push(c);
         }
     }
@@ -101,21 +121,27 @@ fn compute_stuff(inputs: &Vec<Input>) -> i64 {
     return result;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec6() {
-    let lines = read_lines("dec6.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = compute_stuff(&inputs);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec6;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec6_2() {
-    let lines = read_lines("dec6.in.txt").expect("Could not load input.");
-    let inputs = parse_input2(&lines).unwrap();
-    println!("Inputs: {:?}", inputs);
-    let result = compute_stuff(&inputs);
-    println!("{:?}", result);
+/// Numbers are read row-wise in part 1, but column-wise in part 2, so the raw lines are kept (parse only checks them).
+impl Solver for Dec6 {
+    type Input = Vec<String>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<String>> {
+        parse_input(lines)?;
+        parse_input2(lines)?;
+        Ok(lines.to_vec())
+    }
+
+    fn part1(&self, lines: &Vec<String>) -> Result<i64> {
+        let inputs = parse_input(lines)?;
+        Ok(compute_stuff(&inputs))
+    }
+
+    fn part2(&self, lines: &Vec<String>) -> Result<i64> {
+        let inputs = parse_input2(lines)?;
+        Ok(compute_stuff(&inputs))
+    }
 }
@@ -1,7 +1,9 @@
 use std::collections::HashSet;
-use crate::basic_parsing::read_lines;
-use crate::matrix::{read_matrix_from_lines, Matrix};
-use crate::vec2::Vec2;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::matrix::{read_matrix_from_lines, Matrix};
+use aoc_common::solver::Solver;
+use aoc_common::vec2::Vec2;
 
 #[allow(dead_code)]
 fn solve_task(matrix: &Matrix) -> i64 {
@@ -118,20 +120,22 @@ fn solve_task2(matrix: &Matrix) -> i64 {
     return result;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec7() {
-    let lines = read_lines("dec7.in.txt").expect("Could not load input.");
-    let mut matrix = read_matrix_from_lines(lines).unwrap();
-    let result = solve_task(&mut matrix);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec7;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec7_2() {
-    let lines = read_lines("dec7.in.txt").expect("Could not load input.");
-    let mut matrix = read_matrix_from_lines(lines).unwrap();
-    let result = solve_task2(&mut matrix);
-    println!("{:?}", result);
+impl Solver for Dec7 {
+    type Input = Matrix;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Matrix> {
+        read_matrix_from_lines(lines.to_vec())
+    }
+
+    fn part1(&self, matrix: &Matrix) -> Result<i64> {
+        Ok(solve_task(matrix))
+    }
+
+    fn part2(&self, matrix: &Matrix) -> Result<i64> {
+        Ok(solve_task2(matrix))
+    }
 }
@@ -1,15 +1,19 @@
 use std::ops;
-use itertools::Itertools;
-use regex::Regex;
-use crate::basic_parsing::read_lines;
-use crate::find_union::FindUnion;
-use crate::vec2::Vec2;
-
-#[derive(Debug, Clone, Copy)]
-struct Vec3 {
-    x: i64,
-    y: i64,
-    z: i64,
+use aoc_common::basic_parsing::parse_lines;
+use aoc_common::line_struct;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::solver::Solver;
+use aoc_common::spanning_tree::{Edge, Kruskal};
+
+line_struct! {
+    "{x},{y},{z}"
+    #[derive(Debug, Clone, Copy)]
+    pub(crate) struct Vec3 {
+        x: i64,
+        y: i64,
+        z: i64,
+    }
 }
 
 #[allow(dead_code)]
@@ -31,87 +35,80 @@ impl ops::Sub<Self> for Vec3 {
     }
 }
 
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Vec3>> {
-    let mut inputs: Vec<Vec3> = Vec::new();
-    let in_regex = Regex::new(r"^(?<x>\d+),(?<y>\d+),(?<z>\d+)$").unwrap();
-    for (idx, line) in lines.iter().enumerate() {
-        let cap = in_regex.captures(line).ok_or(format!("Line idx={} (zero-based) '{}' does not match the regex.", idx, line))?;
-
-        let input = Vec3 {
-            x: cap["x"].parse::<i64>().unwrap(),
-            y: cap["y"].parse::<i64>().unwrap(),
-            z: cap["z"].parse::<i64>().unwrap(),
-        };
-        inputs.push(input);
-    }
+/// The boxes, and how many of their closest pairs part 1 connects.
+#[derive(Debug, Clone)]
+pub(crate) struct Boxes {
+    boxes: Vec<Vec3>,
+    num_joins: usize,
+}
 
-    Ok(inputs)
+/// The puzzle text, not the input, says how many pairs to connect: 10 in the example, 1000 in the puzzle input.
+fn num_joins(variant: &str) -> usize {
+    if variant.starts_with("ex") { 10 } else { 1000 }
 }
 
-#[allow(dead_code)]
-fn solve_task(inputs: &Vec<Vec3>, num_joins: i32) -> i64 {
+fn parse_input(lines: &[String], variant: &str) -> ParseResult<Boxes> {
+    Ok(Boxes { boxes: parse_lines(lines)?, num_joins: num_joins(variant) })
+}
+
+fn distance(inputs: &[Vec3], i: usize, j: usize) -> i64 {
+    (inputs[j] - inputs[i]).len2()
+}
+
+/// All pairs of boxes (by index), in no particular order. 'Kruskal' sorts them.
+fn all_pairs(inputs: &[Vec3]) -> Vec<Edge<i64>> {
     let mut pair_distances = vec![];
 
     for i in 0..inputs.len() {
         for j in (i + 1)..inputs.len() {
-            let dist = (inputs[j] - inputs[i]).len2();
-            pair_distances.push((Vec2::new(i as i64, j as i64), dist));
+            pair_distances.push(Edge { u: i, v: j, cost: distance(inputs, i, j) });
         }
     }
 
-    pair_distances.sort_by_key(|item| item.1);
-
-    let mut circuits = FindUnion::new();
-    for i in 0..num_joins {
-        let (Vec2 {x, y}, _dist) = pair_distances[i as usize];
-        circuits.join(x, y);
-    }
+    pair_distances
+}
 
-    let sets = circuits.get_sets();
-    let result = sets.iter().map(|s| s.len() as i64).sorted().rev().take(3).product();
+#[allow(dead_code)]
+fn solve_task(inputs: &Vec<Vec3>, num_joins: usize) -> i64 {
+    // Stable sort, so that of the pairs with equal distance at the cut-off those of lower boxes are connected.
+    let mut closest = all_pairs(inputs);
+    closest.sort_by_key(|edge| edge.cost);
+    closest.truncate(num_joins);
+    let mut circuits = Kruskal::new(inputs.len(), closest);
+    circuits.by_ref().for_each(drop);
+
+    let result = circuits.sets().set_sizes().iter().take(3).map(|&size| size as i64).product();
     return result;
     // not 36
 }
 
+/// The join that makes a single circuit is the last merge of Kruskal's algorithm.
 #[allow(dead_code)]
-fn solve_task2(inputs: &Vec<Vec3>) -> i64 {
-    let mut pair_distances = vec![];
+fn solve_task2(inputs: &Vec<Vec3>) -> Option<i64> {
+    let last = Kruskal::new(inputs.len(), all_pairs(inputs)).last().filter(|merge| merge.components == 1)?;
+    Some(inputs[last.edge.u].x * inputs[last.edge.v].x)
+}
 
-    for i in 0..inputs.len() {
-        for j in (i + 1)..inputs.len() {
-            let dist = (inputs[j] - inputs[i]).len2();
-            pair_distances.push((Vec2::new(i as i64, j as i64), dist));
-        }
-    }
+pub(crate) struct Dec8;
 
-    pair_distances.sort_by_key(|item| item.1);
+impl Solver for Dec8 {
+    type Input = Boxes;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-    let mut circuits = FindUnion::new();
-    let mut last_joined = Vec2::new(-1, -1);
-    for (pair, _dist) in pair_distances {
-        if circuits.join(pair.x, pair.y) {
-            //println!("Joined idxs={:?} {:?} {:?}", pair, inputs[pair.x as usize], inputs[pair.y as usize]);
-            last_joined = pair;
-        }
+    fn parse(&self, lines: &[String]) -> ParseResult<Boxes> {
+        parse_input(lines, "in")
     }
 
-    return inputs[last_joined.x as usize].x * inputs[last_joined.y as usize].x;
-}
+    fn parse_variant(&self, lines: &[String], variant: &str) -> ParseResult<Boxes> {
+        parse_input(lines, variant)
+    }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec8() {
-    let lines = read_lines("dec8.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task(&inputs, 1000);
-    println!("{:?}", result);
-}
+    fn part1(&self, inputs: &Boxes) -> Result<i64> {
+        Ok(solve_task(&inputs.boxes, inputs.num_joins))
+    }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec8_2() {
-    let lines = read_lines("dec8.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task2(&inputs);
-    println!("{:?}", result);
+    fn part2(&self, inputs: &Boxes) -> Result<i64> {
+        Ok(solve_task2(&inputs.boxes).ok_or("Boxes never make a single circuit.")?)
+    }
 }
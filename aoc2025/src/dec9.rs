@@ -1,24 +1,14 @@
 use std::cmp::{max, min};
 use itertools::Itertools;
-use regex::Regex;
-use crate::basic_parsing::read_lines;
-use crate::maze::Direction;
-use crate::vec2::Vec2;
-
-fn parse_input(lines: &Vec<String>) -> crate::helpers::Result<Vec<Vec2>> {
-    let mut inputs: Vec<Vec2> = Vec::new();
-    let in_regex = Regex::new(r"^(?<x>\d+),(?<y>\d+)$").unwrap();
-    for (idx, line) in lines.iter().enumerate() {
-        let cap = in_regex.captures(line).ok_or(format!("Line idx={} (zero-based) '{}' does not match the regex.", idx, line))?;
-
-        let input = Vec2 {
-            x: cap["x"].parse::<i32>().unwrap(),
-            y: cap["y"].parse::<i32>().unwrap(),
-        };
-        inputs.push(input);
-    }
-
-    Ok(inputs)
+use aoc_common::basic_parsing::parse_lines_with;
+use aoc_common::parse_error::ParseResult;
+use aoc_common::Result;
+use aoc_common::maze::Direction;
+use aoc_common::solver::Solver;
+use aoc_common::vec2::Vec2;
+
+fn parse_input(lines: &[String]) -> ParseResult<Vec<Vec2>> {
+    parse_lines_with(lines, "{x},{y}", |f| Ok(Vec2::new(f.get("x")?, f.get("y")?)))
 }
 
 fn get_area(p0: Vec2, p1: Vec2) -> i64 {
@@ -244,20 +234,22 @@ fn solve_task2(inputs: &Vec<Vec2>) -> i64 {
     return result;
 }
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec9() {
-    let lines = read_lines("dec9.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task(&inputs);
-    println!("{:?}", result);
-}
+pub(crate) struct Dec9;
 
-#[allow(dead_code)]
-#[allow(unused_variables)]
-pub(crate) fn dec9_2() {
-    let lines = read_lines("dec9.in.txt").expect("Could not load input.");
-    let inputs = parse_input(&lines).unwrap();
-    let result = solve_task2(&inputs);
-    println!("{:?}", result);
+impl Solver for Dec9 {
+    type Input = Vec<Vec2>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(&self, lines: &[String]) -> ParseResult<Vec<Vec2>> {
+        parse_input(lines)
+    }
+
+    fn part1(&self, inputs: &Vec<Vec2>) -> Result<i64> {
+        Ok(solve_task(inputs))
+    }
+
+    fn part2(&self, inputs: &Vec<Vec2>) -> Result<i64> {
+        Ok(solve_task2(inputs))
+    }
 }
@@ -0,0 +1,44 @@
+#[macro_use]
+extern crate maplit;
+
+mod playground;
+
+mod dec1;
+mod dec2;
+mod dec3;
+mod dec4;
+mod dec5;
+mod dec6;
+mod dec7;
+mod dec8;
+mod dec9;
+mod dec10;
+mod dec11;
+
+use aoc_common::runner::{Day, Year};
+
+/// All the solutions, in order.
+pub static DAYS: &[Day] = &[
+    Day { day: 1, solver: &dec1::Dec1 },
+    Day { day: 2, solver: &dec2::Dec2 },
+    Day { day: 3, solver: &dec3::Dec3 },
+    Day { day: 4, solver: &dec4::Dec4 },
+    Day { day: 5, solver: &dec5::Dec5 },
+    Day { day: 6, solver: &dec6::Dec6 },
+    Day { day: 7, solver: &dec7::Dec7 },
+    Day { day: 8, solver: &dec8::Dec8 },
+    Day { day: 9, solver: &dec9::Dec9 },
+    Day { day: 10, solver: &dec10::Dec10 },
+    Day { day: 11, solver: &dec11::Dec11 },
+];
+
+/// Inputs are stored next to the sources of the crate.
+pub const INPUT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+/// Everything the runner needs to know about this year.
+pub static YEAR: Year = Year { days: DAYS, input_dir: INPUT_DIR, play: playground::play };
+
+#[cfg(test)]
+mod answers {
+    include!(concat!(env!("OUT_DIR"), "/answers_tests.rs"));
+}
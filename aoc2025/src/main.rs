@@ -1,28 +1,6 @@
-#[macro_use]
-extern crate maplit;
+use std::process::ExitCode;
+use aoc_common::runner;
 
-mod helpers;
-mod basic_parsing;
-mod vec2;
-mod matrix;
-mod maze;
-mod find_union;
-
-mod playground;
-
-mod dec1;
-mod dec2;
-mod dec3;
-mod dec4;
-mod dec5;
-mod dec6;
-mod dec7;
-mod dec8;
-mod dec9;
-mod dec10;
-mod dec11;
-
-fn main() {
-    //playground::play();
-    dec11::dec11_2();
+fn main() -> ExitCode {
+    runner::main(&aoc2025::YEAR)
 }